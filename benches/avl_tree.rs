@@ -0,0 +1,26 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use ds_and_algos::datastructures::avl_tree::AVLTree;
+
+mod common;
+
+use common::LENS;
+
+pub fn bulk_loads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("AVLTree bulk loads of sorted values");
+
+    for len in LENS {
+        group.bench_function(format!("from_iter with {} items", len), |b| {
+            b.iter(|| AVLTree::from_iter(0..len))
+        });
+
+        group.bench_function(format!("from_sorted_iter with {} items", len), |b| {
+            b.iter(|| AVLTree::from_sorted_iter(0..len))
+        });
+    }
+
+    group.finish();
+}
+
+// Inserts and removes are benchmarked against the other ordered sets in `ordered_set`
+criterion_group!(benches, bulk_loads);
+criterion_main!(benches);
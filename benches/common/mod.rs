@@ -0,0 +1,27 @@
+// Every bench only pulls in the fixtures it needs
+#![allow(dead_code)]
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Each benchmark applies this many operations to a structure of the given size, so with
+/// logarithmic operations the time should only grow by a constant step per 10x
+pub const OPERATIONS: i32 = 1000;
+
+pub const LENS: [i32; 3] = [1000, 10000, 100000];
+
+/// Keys in a fixed shuffled order so that no structure sees sorted inserts
+pub fn shuffled_keys(len: i32) -> Vec<i32> {
+    return shuffled((0..len).collect());
+}
+
+/// Even keys in a fixed shuffled order to build from, leaving the odd keys free
+pub fn shuffled_even_keys(len: i32) -> Vec<i32> {
+    return shuffled((0..len).map(|k| k * 2).collect());
+}
+
+fn shuffled(mut keys: Vec<i32>) -> Vec<i32> {
+    keys.shuffle(&mut StdRng::seed_from_u64(0));
+    return keys;
+}
@@ -12,6 +12,29 @@ pub fn get_last_safe_list(c: &mut Criterion) {
     });
 }
 
+pub fn back_and_push_back_safe_list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Back and push_back on safe::SinglyLinkedList");
+
+    // Both should stay flat as the list grows since the tail is tracked
+    for len in [1000, 10000, 100000] {
+        let mut list =
+            ds_and_algos::datastructures::singly_linked_list::SinglyLinkedList::from_iter(0..len);
+
+        group.bench_function(format!("back with {} items", len), |b| {
+            b.iter(|| list.back().copied())
+        });
+
+        group.bench_function(format!("push_back with {} items", len), |b| {
+            b.iter(|| {
+                list.push_back(len);
+                list.pop_front()
+            })
+        });
+    }
+
+    group.finish();
+}
+
 pub fn get_last_unsafe_list(c: &mut Criterion) {
     let vec: Vec<usize> = (0..1000).collect();
     let list = SinglyLinkedList::from_iter(vec);
@@ -33,6 +56,7 @@ pub fn get_last_unsafe_doubly_list(c: &mut Criterion) {
 criterion_group!(
     benches,
     get_last_safe_list,
+    back_and_push_back_safe_list,
     get_last_unsafe_list,
     get_last_unsafe_doubly_list
 );
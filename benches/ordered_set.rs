@@ -0,0 +1,133 @@
+use criterion::measurement::WallTime;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkGroup, Criterion};
+use ds_and_algos::datastructures::avl_tree::AVLTree;
+use ds_and_algos::datastructures::ordered_set::OrderedSet;
+use ds_and_algos::datastructures::red_black_tree::RedBlackTree;
+
+mod common;
+
+use common::{shuffled_even_keys, LENS, OPERATIONS};
+
+fn build<S>(keys: &[i32]) -> S
+where
+    S: OrderedSet<i32> + Default,
+{
+    let mut set = S::default();
+    for k in keys {
+        set.insert(*k);
+    }
+    return set;
+}
+
+fn bench_inserts<S>(group: &mut BenchmarkGroup<WallTime>, name: &str)
+where
+    S: OrderedSet<i32> + Default,
+{
+    for len in LENS {
+        let keys = shuffled_even_keys(len);
+        let step = len / OPERATIONS;
+        let new_keys: Vec<i32> = (0..OPERATIONS).map(|i| i * step * 2 + 1).collect();
+
+        group.bench_function(format!("{} with {} items", name, len), |b| {
+            b.iter_batched(
+                || build::<S>(&keys),
+                |mut set| {
+                    for k in new_keys.iter() {
+                        set.insert(*k);
+                    }
+                    set
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+}
+
+fn bench_removes<S>(group: &mut BenchmarkGroup<WallTime>, name: &str)
+where
+    S: OrderedSet<i32> + Default,
+{
+    for len in LENS {
+        let keys = shuffled_even_keys(len);
+        let removed_keys: Vec<i32> = keys.iter().copied().take(OPERATIONS as usize).collect();
+
+        group.bench_function(format!("{} with {} items", name, len), |b| {
+            b.iter_batched(
+                || build::<S>(&keys),
+                |mut set| {
+                    for k in removed_keys.iter() {
+                        black_box(set.remove(k));
+                    }
+                    set
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+}
+
+fn bench_lookups<S>(group: &mut BenchmarkGroup<WallTime>, name: &str)
+where
+    S: OrderedSet<i32> + Default,
+{
+    for len in LENS {
+        let keys = shuffled_even_keys(len);
+        let set = build::<S>(&keys);
+
+        // Half of the lookups hit and half miss
+        let step = len / OPERATIONS;
+        let lookups: Vec<i32> = (0..OPERATIONS).map(|i| i * step + i % 2).collect();
+
+        group.bench_function(format!("{} with {} items", name, len), |b| {
+            b.iter(|| {
+                for k in lookups.iter() {
+                    black_box(set.contains(k));
+                }
+            })
+        });
+    }
+}
+
+fn bench_iters<S>(group: &mut BenchmarkGroup<WallTime>, name: &str)
+where
+    S: OrderedSet<i32> + Default,
+{
+    for len in LENS {
+        let set = build::<S>(&shuffled_even_keys(len));
+
+        group.bench_function(format!("{} with {} items", name, len), |b| {
+            b.iter(|| set.iter().fold(0i64, |sum, k| sum + *k as i64))
+        });
+    }
+}
+
+pub fn inserts(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("OrderedSet {} inserts", OPERATIONS));
+    bench_inserts::<AVLTree<i32>>(&mut group, "AVLTree");
+    bench_inserts::<RedBlackTree<i32>>(&mut group, "RedBlackTree");
+    group.finish();
+}
+
+pub fn removes(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("OrderedSet {} removes", OPERATIONS));
+    bench_removes::<AVLTree<i32>>(&mut group, "AVLTree");
+    bench_removes::<RedBlackTree<i32>>(&mut group, "RedBlackTree");
+    group.finish();
+}
+
+pub fn lookups(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("OrderedSet {} lookups", OPERATIONS));
+    bench_lookups::<AVLTree<i32>>(&mut group, "AVLTree");
+    bench_lookups::<RedBlackTree<i32>>(&mut group, "RedBlackTree");
+    group.finish();
+}
+
+pub fn iters(c: &mut Criterion) {
+    let mut group = c.benchmark_group("OrderedSet full iterations");
+    bench_iters::<AVLTree<i32>>(&mut group, "AVLTree");
+    bench_iters::<RedBlackTree<i32>>(&mut group, "RedBlackTree");
+    group.finish();
+}
+
+criterion_group!(benches, inserts, removes, lookups, iters);
+criterion_main!(benches);
@@ -1,4 +1,5 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ds_and_algos::datastructures::linked_lists::unrolled_linked_list;
 use ds_and_algos::datastructures::linked_lists::unsafe_doubly_linked_list;
 use ds_and_algos::datastructures::linked_lists::unsafe_singly_linked_list;
 
@@ -28,9 +29,41 @@ pub fn traverse_doubly_linked_list(c: &mut Criterion) {
     });
 }
 
+pub fn traverse_unrolled_linked_list(c: &mut Criterion) {
+    let vec: Vec<_> = (0..10000).collect();
+    let list = unrolled_linked_list::UnrolledLinkedList::<usize>::from_iter(vec.clone());
+    c.bench_function("Traverse unrolled_linked_list", |b| {
+        b.iter(|| {
+            let mut n = 0;
+            for i in 0..list.len() {
+                n += list.get(i).unwrap();
+            }
+        })
+    });
+}
+
+pub fn iterate_linked_lists(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Iterate linked lists");
+    let vec: Vec<usize> = (0..10000).collect();
+
+    let list = unsafe_doubly_linked_list::DoublyLinkedList::from_iter(vec.clone());
+    group.bench_function("doubly_linked_list", |b| {
+        b.iter(|| black_box(list.iter().sum::<usize>()))
+    });
+
+    let list = unrolled_linked_list::UnrolledLinkedList::<usize>::from_iter(vec.clone());
+    group.bench_function("unrolled_linked_list", |b| {
+        b.iter(|| black_box(list.iter().sum::<usize>()))
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     traverse_singly_linked_list,
-    traverse_doubly_linked_list
+    traverse_doubly_linked_list,
+    traverse_unrolled_linked_list,
+    iterate_linked_lists
 );
 criterion_main!(benches);
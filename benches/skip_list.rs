@@ -0,0 +1,70 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ds_and_algos::datastructures::{avl_tree::AVLTree, skip_list::SkipList};
+
+mod common;
+
+use common::{shuffled_keys, OPERATIONS};
+
+pub fn ordered_lookups(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Ordered lookups");
+
+    for len in [1000, 10000] {
+        let keys = shuffled_keys(len);
+
+        let mut skip_list = SkipList::with_seed(0);
+        keys.iter().for_each(|k| {
+            skip_list.insert(*k, *k);
+        });
+        group.bench_function(format!("SkipList with {} items", len), |b| {
+            b.iter(|| {
+                for k in keys.iter().take(OPERATIONS as usize) {
+                    black_box(skip_list.get(k));
+                }
+            })
+        });
+
+        let tree = AVLTree::from_iter(keys.iter().copied());
+        group.bench_function(format!("AVLTree with {} items", len), |b| {
+            b.iter(|| {
+                for k in keys.iter().take(OPERATIONS as usize) {
+                    black_box(tree.contains(k));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+pub fn inserts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Inserts in random order");
+
+    for len in [1000, 10000] {
+        let keys = shuffled_keys(len);
+
+        group.bench_function(format!("SkipList with {} items", len), |b| {
+            b.iter(|| {
+                let mut skip_list = SkipList::with_seed(0);
+                for k in keys.iter() {
+                    skip_list.insert(*k, *k);
+                }
+                black_box(skip_list.len())
+            })
+        });
+
+        group.bench_function(format!("AVLTree with {} items", len), |b| {
+            b.iter(|| {
+                let mut tree = AVLTree::new();
+                for k in keys.iter() {
+                    tree.insert(*k);
+                }
+                black_box(tree.len())
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, ordered_lookups, inserts);
+criterion_main!(benches);
@@ -0,0 +1,89 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ds_and_algos::datastructures::{array_list::ArrayList, small_array_list::SmallArrayList};
+
+pub fn push_and_read_small_lists(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Push and read lists");
+
+    for len in [4, 8, 16] {
+        group.bench_function(format!("SmallArrayList<_, 8> with {} items", len), |b| {
+            b.iter(|| {
+                let mut list: SmallArrayList<usize, 8> = SmallArrayList::new();
+                for n in 0..len {
+                    list.push(n);
+                }
+                black_box(list.as_slice().iter().sum::<usize>())
+            })
+        });
+
+        group.bench_function(format!("ArrayList with {} items", len), |b| {
+            b.iter(|| {
+                let mut list = ArrayList::new();
+                for n in 0..len {
+                    list.push(n);
+                }
+                black_box(list.as_slice().iter().sum::<usize>())
+            })
+        });
+
+        group.bench_function(format!("Vec with {} items", len), |b| {
+            b.iter(|| {
+                let mut list = Vec::new();
+                for n in 0..len {
+                    list.push(n);
+                }
+                black_box(list.as_slice().iter().sum::<usize>())
+            })
+        });
+    }
+
+    group.finish();
+}
+
+pub fn insert_and_remove_small_lists(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Insert and remove at the front of lists");
+
+    group.bench_function("SmallArrayList<_, 8>", |b| {
+        b.iter(|| {
+            let mut list: SmallArrayList<usize, 8> = SmallArrayList::new();
+            for n in 0..8 {
+                list.insert(0, n);
+            }
+            while !list.is_empty() {
+                black_box(list.remove(0));
+            }
+        })
+    });
+
+    group.bench_function("ArrayList", |b| {
+        b.iter(|| {
+            let mut list = ArrayList::new();
+            for n in 0..8 {
+                list.insert(0, n);
+            }
+            while !list.is_empty() {
+                black_box(list.remove(0));
+            }
+        })
+    });
+
+    group.bench_function("Vec", |b| {
+        b.iter(|| {
+            let mut list = Vec::new();
+            for n in 0..8 {
+                list.insert(0, n);
+            }
+            while !list.is_empty() {
+                black_box(list.remove(0));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    push_and_read_small_lists,
+    insert_and_remove_small_lists
+);
+criterion_main!(benches);
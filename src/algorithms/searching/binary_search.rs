@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 pub fn binary_search_recur<T>(slice: &[T], target: &T) -> Option<usize>
 where
     T: PartialEq + PartialOrd,
@@ -50,6 +52,43 @@ where
     return None;
 }
 
+/// Searches a sorted `slice` for `target`.
+///
+/// Returns `Ok` with the index of a matching element, or `Err` with the index where
+/// `target` could be inserted while keeping the slice sorted.
+pub fn binary_search<T>(slice: &[T], target: &T) -> Result<usize, usize>
+where
+    T: Ord,
+{
+    binary_search_by(slice, |value| value.cmp(target))
+}
+
+/// Searches a sorted `slice` with a `compare` function that returns the ordering of an
+/// element relative to the target.
+///
+/// Returns `Ok` with the index of a matching element, or `Err` with the index where
+/// the target could be inserted while keeping the slice sorted.
+pub fn binary_search_by<T, F>(slice: &[T], mut compare: F) -> Result<usize, usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    // Search in the half open range `[lo, hi)` so that an empty slice needs no special case
+    let mut lo = 0;
+    let mut hi = slice.len();
+
+    while lo < hi {
+        let mid = lo + ((hi - lo) / 2);
+
+        match compare(&slice[mid]) {
+            Ordering::Equal => return Ok(mid),
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+        }
+    }
+
+    return Err(lo);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +116,32 @@ mod tests {
         assert_eq!(binary_search_iter(&nums, &5), None);
         assert_eq!(binary_search_iter(&nums, &-1), None);
     }
+
+    #[test]
+    fn can_binary_search() {
+        let nums = [0, 2, 4, 6, 8];
+
+        assert_eq!(binary_search(&nums, &0), Ok(0));
+        assert_eq!(binary_search(&nums, &4), Ok(2));
+        assert_eq!(binary_search(&nums, &8), Ok(4));
+
+        // Insertion points
+        assert_eq!(binary_search(&nums, &-1), Err(0));
+        assert_eq!(binary_search(&nums, &5), Err(3));
+        assert_eq!(binary_search(&nums, &9), Err(5));
+        assert_eq!(binary_search(&[], &1), Err(0));
+    }
+
+    #[test]
+    fn can_binary_search_by() {
+        let pairs = [(0, 'a'), (2, 'b'), (4, 'c')];
+
+        assert_eq!(binary_search_by(&pairs, |(k, _)| k.cmp(&2)), Ok(1));
+        assert_eq!(binary_search_by(&pairs, |(k, _)| k.cmp(&3)), Err(2));
+
+        // Reverse ordering
+        let nums = [8, 6, 4, 2, 0];
+        assert_eq!(binary_search_by(&nums, |n| 6.cmp(n)), Ok(1));
+        assert_eq!(binary_search_by(&nums, |n| 5.cmp(n)), Err(2));
+    }
 }
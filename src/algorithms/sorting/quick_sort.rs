@@ -2,85 +2,92 @@ pub fn quick_sort<T>(slice: &mut [T])
 where
     T: PartialOrd,
 {
-    if slice.len() <= 1 {
-        return;
-    }
-
-    let p = partition(slice);
-    quick_sort(&mut slice[..p]);
-    quick_sort(&mut slice[p..]);
+    quick_sort_by(slice, |a, b| a < b);
 }
 
 pub fn quick_sort_by<T, F>(slice: &mut [T], mut is_less: F)
 where
-    T: PartialOrd,
-    F: FnMut(&T, &T) -> bool + Copy,
+    F: FnMut(&T, &T) -> bool,
 {
-    if slice.len() <= 1 {
-        return;
-    }
-
-    let p = partition_by(slice, &mut is_less);
-    quick_sort_by(&mut slice[..p], is_less);
-    quick_sort_by(&mut slice[p..], is_less);
+    sort_recur(slice, &mut is_less);
 }
 
-fn partition<T>(slice: &mut [T]) -> usize
+/// Only recurses into the smaller partition and loops on the larger one, which keeps the
+/// stack depth at O(log n) even when the pivots are bad
+fn sort_recur<T, F>(mut slice: &mut [T], is_less: &mut F)
 where
-    T: PartialOrd,
+    F: FnMut(&T, &T) -> bool,
 {
-    // TODO: Randomly select pivot point
-    let p = slice.len() - 1;
-
-    // Move pivot point to end of slice
-    slice.swap(p, slice.len() - 1);
-
-    let mut l = 0;
-    let mut r = 0;
-
-    while r < p {
-        if slice[r] < slice[p] {
-            slice.swap(l, r);
-            l += 1;
+    while slice.len() > 1 {
+        // The pivot is already in its final position
+        let p = partition_by(slice, is_less);
+        let (left, right) = std::mem::take(&mut slice).split_at_mut(p);
+        let right = &mut right[1..];
+
+        if left.len() < right.len() {
+            sort_recur(left, is_less);
+            slice = right;
+        } else {
+            sort_recur(right, is_less);
+            slice = left;
         }
-        r += 1;
     }
-
-    slice.swap(p, l);
-
-    p
 }
 
-fn partition_by<T, F>(slice: &mut [T], mut is_less: F) -> usize
+/// Partitions the slice around a median of three pivot and returns the pivot's index
+fn partition_by<T, F>(slice: &mut [T], is_less: &mut F) -> usize
 where
-    T: PartialOrd,
     F: FnMut(&T, &T) -> bool,
 {
-    // TODO: Randomly select pivot point
-    let p = slice.len() - 1;
-
-    // Move pivot point to end of slice
-    slice.swap(p, slice.len() - 1);
+    let last = slice.len() - 1;
+    let mid = slice.len() / 2;
 
-    let mut l = 0;
-    let mut r = 0;
-
-    while r < p {
-        if is_less(&slice[r], &slice[p]) {
-            slice.swap(l, r);
+    // Order the first, middle and last element, so the median ends up in the middle.
+    // Sorted input then splits in half instead of peeling off one element at a time.
+    if is_less(&slice[mid], &slice[0]) {
+        slice.swap(mid, 0);
+    }
+    if is_less(&slice[last], &slice[mid]) {
+        slice.swap(last, mid);
+        if is_less(&slice[mid], &slice[0]) {
+            slice.swap(mid, 0);
+        }
+    }
+    slice.swap(0, mid);
+
+    // Scan from both ends with the pivot parked at the front. Both scans stop at elements
+    // equal to the pivot, so runs of equal elements are split evenly instead of all
+    // landing on one side.
+    let mut l = 1;
+    let mut r = last;
+    loop {
+        while l <= r && is_less(&slice[l], &slice[0]) {
             l += 1;
         }
-        r += 1;
+        while l <= r && is_less(&slice[0], &slice[r]) {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+
+        slice.swap(l, r);
+        l += 1;
+        r -= 1;
     }
 
-    slice.swap(p, l);
+    // slice[r] is not greater than the pivot, so it can take the pivot's place at the front
+    slice.swap(0, r);
 
-    p
+    // Index of the pivot after partitioning
+    r
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn can_sort() {
@@ -106,6 +113,14 @@ mod tests {
         assert_eq!(nums, [1, 2, 3, 4, 5, 6]);
     }
 
+    #[test]
+    fn can_sort_when_pivot_is_not_the_max() {
+        let mut nums = [5, 1, 9, 2, 7, 3, 3, 0];
+        quick_sort(&mut nums[..]);
+
+        assert_eq!(nums, [0, 1, 2, 3, 3, 5, 7, 9]);
+    }
+
     #[test]
     fn can_sort_by() {
         let mut nums = [3, 1, 2, 4, 6, 5];
@@ -129,4 +144,48 @@ mod tests {
 
         assert_eq!(nums, [1, 2, 3, 4, 5, 6]);
     }
+
+    #[test]
+    fn can_sort_by_when_pivot_is_not_the_max() {
+        let mut nums = [5, 1, 9, 2, 7, 3, 3, 0];
+        quick_sort_by(&mut nums[..], |a, b| a > b);
+
+        assert_eq!(nums, [9, 7, 5, 3, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn can_sort_large_sorted_and_equal_input() {
+        let mut nums: Vec<i32> = (0..100_000).collect();
+        quick_sort(&mut nums[..]);
+        assert!(nums.iter().copied().eq(0..100_000));
+
+        quick_sort_by(&mut nums[..], |a, b| a > b);
+        assert!(nums.iter().copied().eq((0..100_000).rev()));
+
+        let mut nums = vec![7; 100_000];
+        quick_sort(&mut nums[..]);
+        assert!(nums.iter().all(|n| *n == 7));
+    }
+
+    #[test]
+    fn can_sort_random_input_like_std() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for len in 0..200 {
+            let mut nums: Vec<i32> = (0..len).map(|_| rng.gen_range(0..20)).collect();
+            let mut expected = nums.clone();
+            expected.sort();
+
+            quick_sort(&mut nums[..]);
+            assert_eq!(nums, expected);
+        }
+    }
+
+    #[test]
+    fn can_sort_with_duplicates() {
+        let mut nums = [3, 1, 3, 0, 3, 2, 1, 3, 0, 2];
+        quick_sort(&mut nums[..]);
+
+        assert_eq!(nums, [0, 0, 1, 1, 2, 2, 3, 3, 3, 3]);
+    }
 }
@@ -0,0 +1,142 @@
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::ptr::{self, NonNull};
+
+/// The error returned when an `Allocator` fails to satisfy an allocation request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl std::error::Error for AllocError {}
+
+/// A minimal, stable version of the unstable `std::alloc::Allocator` trait used by the
+/// collections in this crate.
+///
+/// # Safety
+///
+/// Implementors must return blocks of memory that are valid for reads and writes of
+/// `layout.size()` bytes and aligned to `layout.align()`. The collections never request
+/// zero-sized allocations.
+///
+/// Blocks must stay valid until they are passed to `deallocate` on *any* value of the
+/// allocator type that is still alive. Collections rely on this to move nodes between
+/// each other, such as when splicing two linked lists together.
+pub unsafe trait Allocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by this allocator, or by another value of the same
+    /// allocator type that is still alive, with the same `layout`. It must not have been
+    /// deallocated already.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// Grows the block at `ptr` to `new_layout`, preserving the contents of the old block.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by this allocator, or by another value of the same
+    /// allocator type that is still alive, with `old_layout`. It must not have been
+    /// deallocated already, and `new_layout.size()` must be greater than or equal to
+    /// `old_layout.size()`.
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        debug_assert!(new_layout.size() >= old_layout.size());
+
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_layout.size());
+        self.deallocate(ptr, old_layout);
+
+        return Ok(new_ptr);
+    }
+}
+
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        (**self).deallocate(ptr, layout)
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        (**self).grow(ptr, old_layout, new_layout)
+    }
+}
+
+/// The global memory allocator, backed by `std::alloc`. This is the default allocator of
+/// every collection in this crate.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        debug_assert!(
+            layout.size() > 0,
+            "Zero sized allocations are not supported"
+        );
+        NonNull::new(unsafe { alloc::alloc(layout) }).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        alloc::dealloc(ptr.as_ptr(), layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        // `realloc` can only keep the alignment of the old layout
+        if old_layout.align() != new_layout.align() {
+            let new_ptr = self.allocate(new_layout)?;
+            ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_layout.size());
+            self.deallocate(ptr, old_layout);
+            return Ok(new_ptr);
+        }
+
+        NonNull::new(alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size())).ok_or(AllocError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_allocate_grow_and_deallocate() {
+        let layout = Layout::array::<u32>(2).unwrap();
+        let new_layout = Layout::array::<u32>(4).unwrap();
+
+        unsafe {
+            let ptr = Global.allocate(layout).unwrap().cast::<u32>();
+            ptr.as_ptr().write(1);
+            ptr.as_ptr().add(1).write(2);
+
+            let ptr = Global
+                .grow(ptr.cast(), layout, new_layout)
+                .unwrap()
+                .cast::<u32>();
+            assert_eq!(ptr.as_ptr().read(), 1);
+            assert_eq!(ptr.as_ptr().add(1).read(), 2);
+
+            Global.deallocate(ptr.cast(), new_layout);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::alloc::{self, Layout};
+use std::cell::{Cell, RefCell};
+use std::ptr::{self, NonNull};
+
+use super::{AllocError, Allocator};
+
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+/// An arena allocator that hands out memory by bumping a pointer through large chunks.
+///
+/// Deallocating a single block is a no-op, all of the memory is released at once when the
+/// arena is reset or dropped. This makes it a good fit for node heavy structures such as
+/// `DoublyLinkedList<T, &BumpAllocator>` that get thrown away as a whole.
+///
+/// Only `&BumpAllocator` implements `Allocator`. Borrowing the arena guarantees that it
+/// outlives every collection using it, even when nodes are moved between collections that
+/// were allocated from different arenas.
+///
+/// NOTE: Resetting or dropping the arena does not run the destructors of the values that
+/// were stored in it, that is still the job of the collections using it.
+pub struct BumpAllocator {
+    chunks: RefCell<Vec<(NonNull<u8>, Layout)>>,
+    ptr: Cell<*mut u8>,
+    end: Cell<*mut u8>,
+    chunk_size: usize,
+}
+
+impl BumpAllocator {
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            chunks: RefCell::new(Vec::new()),
+            ptr: Cell::new(ptr::null_mut()),
+            end: Cell::new(ptr::null_mut()),
+            chunk_size: chunk_size.max(1),
+        }
+    }
+
+    /// Returns the total number of bytes that this arena has requested from the
+    /// global allocator
+    pub fn allocated_bytes(&self) -> usize {
+        return self
+            .chunks
+            .borrow()
+            .iter()
+            .map(|(_, layout)| layout.size())
+            .sum();
+    }
+
+    /// Frees every chunk of the arena in one shot so that it can be reused.
+    ///
+    /// Taking `&mut self` guarantees that no collection is still borrowing the arena.
+    pub fn reset(&mut self) {
+        for (chunk, layout) in self.chunks.get_mut().drain(..) {
+            unsafe { alloc::dealloc(chunk.as_ptr(), layout) };
+        }
+
+        self.ptr.set(ptr::null_mut());
+        self.end.set(ptr::null_mut());
+    }
+}
+
+// Helper methods
+impl BumpAllocator {
+    /// Returns the number of bytes left in the current chunk
+    fn remaining(&self) -> usize {
+        return self.end.get() as usize - self.ptr.get() as usize;
+    }
+
+    /// Tries to carve out `layout` from the current chunk
+    fn bump(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let ptr = self.ptr.get();
+        if ptr.is_null() {
+            return None;
+        }
+
+        let padding = ptr.align_offset(layout.align());
+        let needed = padding.checked_add(layout.size())?;
+        if needed > self.remaining() {
+            return None;
+        }
+
+        unsafe {
+            let block = ptr.add(padding);
+            self.ptr.set(block.add(layout.size()));
+            return Some(NonNull::new_unchecked(block));
+        }
+    }
+
+    fn push_chunk(&self, layout: Layout) -> Result<(), AllocError> {
+        // Leave enough room to align the block inside of the new chunk
+        let size = layout
+            .size()
+            .checked_add(layout.align())
+            .ok_or(AllocError)?
+            .max(self.chunk_size);
+        let chunk_layout = Layout::from_size_align(size, layout.align()).map_err(|_| AllocError)?;
+
+        let chunk = NonNull::new(unsafe { alloc::alloc(chunk_layout) }).ok_or(AllocError)?;
+        self.chunks.borrow_mut().push((chunk, chunk_layout));
+
+        self.ptr.set(chunk.as_ptr());
+        self.end.set(unsafe { chunk.as_ptr().add(size) });
+
+        return Ok(());
+    }
+}
+
+unsafe impl Allocator for &BumpAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        if let Some(block) = self.bump(layout) {
+            return Ok(block);
+        }
+
+        self.push_chunk(layout)?;
+        self.bump(layout).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+        // Memory is only released when the whole arena is reset or dropped
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        // The most recent allocation can be grown in place if the chunk has room for it
+        let is_last_block = ptr.as_ptr().add(old_layout.size()) == self.ptr.get();
+        let is_aligned = ptr.as_ptr().align_offset(new_layout.align()) == 0;
+        let extra = new_layout.size() - old_layout.size();
+
+        if is_last_block && is_aligned && extra <= self.remaining() {
+            self.ptr.set(self.ptr.get().add(extra));
+            return Ok(ptr);
+        }
+
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), old_layout.size());
+
+        return Ok(new_ptr);
+    }
+}
+
+impl Default for BumpAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for BumpAllocator {
+    fn drop(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_allocate_aligned_blocks() {
+        let arena = BumpAllocator::with_chunk_size(64);
+
+        let byte = (&arena).allocate(Layout::new::<u8>()).unwrap();
+        let word = (&arena).allocate(Layout::new::<u64>()).unwrap();
+
+        assert_eq!(word.as_ptr().align_offset(Layout::new::<u64>().align()), 0);
+        assert_ne!(byte, word);
+        assert_eq!(arena.allocated_bytes(), 64);
+    }
+
+    #[test]
+    fn can_allocate_blocks_larger_than_a_chunk() {
+        let arena = BumpAllocator::with_chunk_size(16);
+
+        let layout = Layout::array::<u64>(8).unwrap();
+        let block = (&arena).allocate(layout).unwrap();
+
+        unsafe {
+            let block = block.cast::<u64>().as_ptr();
+            for i in 0..8 {
+                block.add(i).write(i as u64);
+            }
+            for i in 0..8 {
+                assert_eq!(block.add(i).read(), i as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn can_grow_last_block_in_place() {
+        let arena = BumpAllocator::with_chunk_size(256);
+
+        let old_layout = Layout::array::<u32>(4).unwrap();
+        let new_layout = Layout::array::<u32>(8).unwrap();
+
+        unsafe {
+            let block = (&arena).allocate(old_layout).unwrap();
+            block.cast::<u32>().as_ptr().write(42);
+
+            let grown = (&arena).grow(block, old_layout, new_layout).unwrap();
+            assert_eq!(grown, block);
+            assert_eq!(grown.cast::<u32>().as_ptr().read(), 42);
+
+            // Not the last block anymore, so it has to be copied
+            (&arena).allocate(old_layout).unwrap();
+            let moved = (&arena)
+                .grow(grown, new_layout, Layout::array::<u32>(16).unwrap())
+                .unwrap();
+            assert_ne!(moved, grown);
+            assert_eq!(moved.cast::<u32>().as_ptr().read(), 42);
+        }
+    }
+
+    #[test]
+    fn can_reset() {
+        let mut arena = BumpAllocator::with_chunk_size(32);
+
+        for _ in 0..10 {
+            (&arena).allocate(Layout::new::<[u8; 16]>()).unwrap();
+        }
+        assert!(arena.allocated_bytes() >= 160);
+
+        arena.reset();
+        assert_eq!(arena.allocated_bytes(), 0);
+
+        (&arena).allocate(Layout::new::<u8>()).unwrap();
+        assert_eq!(arena.allocated_bytes(), 32);
+    }
+}
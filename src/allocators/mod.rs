@@ -0,0 +1,5 @@
+pub mod allocator;
+pub mod bump_allocator;
+
+pub use allocator::*;
+pub use bump_allocator::*;
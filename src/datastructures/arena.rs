@@ -0,0 +1,110 @@
+/// A free-list arena for the index linked datastructures. Freed slots are reused before
+/// the arena grows, so the index of a value stays valid until it is freed.
+#[derive(Debug, Clone)]
+pub(crate) struct Arena<T> {
+    slots: Vec<Option<T>>,
+    free_slots: Vec<usize>,
+}
+
+impl<T> Arena<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+        }
+    }
+
+    /// Stores `value` in a free slot and returns its index
+    pub(crate) fn alloc(&mut self, value: T) -> usize {
+        match self.free_slots.pop() {
+            Some(slot) => {
+                self.slots[slot] = Some(value);
+                return slot;
+            }
+            None => {
+                self.slots.push(Some(value));
+                return self.slots.len() - 1;
+            }
+        }
+    }
+
+    /// Takes the value out of `slot`. Panics if the slot is already free.
+    pub(crate) fn free(&mut self, slot: usize) -> T {
+        let value = self.slots[slot].take().unwrap();
+        self.free_slots.push(slot);
+
+        // Give the memory back once every slot is free so the arena doesn't only ever grow
+        if self.free_slots.len() == self.slots.len() {
+            self.clear();
+        }
+
+        return value;
+    }
+
+    pub(crate) fn get(&self, slot: usize) -> &T {
+        self.slots[slot].as_ref().unwrap()
+    }
+
+    pub(crate) fn get_mut(&mut self, slot: usize) -> &mut T {
+        self.slots[slot].as_mut().unwrap()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.slots.clear();
+        self.free_slots.clear();
+    }
+
+    /// Returns the number of slots, free or not
+    pub(crate) fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns all slots, where the free ones are `None`
+    pub(crate) fn slots_mut(&mut self) -> &mut [Option<T>] {
+        &mut self.slots
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_reuse_freed_slots() {
+        let mut arena = Arena::new();
+        let a = arena.alloc('a');
+        let b = arena.alloc('b');
+        arena.alloc('c');
+
+        assert_eq!(arena.free(b), 'b');
+        assert_eq!(arena.alloc('d'), b);
+        assert_eq!(arena.capacity(), 3);
+
+        *arena.get_mut(a) = 'e';
+        assert_eq!(arena.get(a), &'e');
+        assert_eq!(arena.get(b), &'d');
+    }
+
+    #[test]
+    fn can_release_memory_once_empty() {
+        let mut arena = Arena::new();
+        let slots: Vec<_> = (0..4).map(|value| arena.alloc(value)).collect();
+
+        for slot in slots {
+            arena.free(slot);
+        }
+        assert_eq!(arena.capacity(), 0);
+        assert_eq!(arena.alloc(7), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn can_panic_on_double_free() {
+        let mut arena = Arena::new();
+        let slot = arena.alloc(1);
+        arena.alloc(2);
+
+        arena.free(slot);
+        arena.free(slot);
+    }
+}
@@ -0,0 +1,553 @@
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use std::sync::atomic;
+
+use super::unsafe_doubly_linked_list::{
+    link_node_after, link_node_before, unlink_node, LinkOps, NEXT_LIST_ID,
+};
+
+/// The pointers that put a value into an `IntrusiveList`. A struct embeds one `Link`
+/// for every list it should be able to be part of at the same time.
+pub struct Link<T> {
+    next: Cell<Option<NonNull<T>>>,
+    prev: Cell<Option<NonNull<T>>>,
+    /// Id of the list the value is linked into, or `0` if it is not linked
+    owner: Cell<u64>,
+}
+
+impl<T> Link<T> {
+    pub const fn new() -> Self {
+        Self {
+            next: Cell::new(None),
+            prev: Cell::new(None),
+            owner: Cell::new(0),
+        }
+    }
+
+    pub fn is_linked(&self) -> bool {
+        return self.owner.get() != 0;
+    }
+}
+
+impl<T> Default for Link<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for Link<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Link")
+            .field("linked", &self.is_linked())
+            .finish()
+    }
+}
+
+/// Tells an `IntrusiveList` which `Link` of its values to use.
+///
+/// # Safety
+///
+/// `link` must return the same `Link` every time it is called with the same value, and
+/// no two values may share a `Link`.
+pub unsafe trait Adapter {
+    type Value;
+
+    fn link(value: &Self::Value) -> &Link<Self::Value>;
+}
+
+/// Lets the relinking helpers of `unsafe_doubly_linked_list` follow the links that
+/// the adapter `A` picks
+struct Ops<A>(PhantomData<A>);
+
+impl<A: Adapter> LinkOps for Ops<A> {
+    type Node = A::Value;
+
+    unsafe fn next(node: NonNull<A::Value>) -> Option<NonNull<A::Value>> {
+        A::link(node.as_ref()).next.get()
+    }
+
+    unsafe fn prev(node: NonNull<A::Value>) -> Option<NonNull<A::Value>> {
+        A::link(node.as_ref()).prev.get()
+    }
+
+    unsafe fn set_next(node: NonNull<A::Value>, next: Option<NonNull<A::Value>>) {
+        A::link(node.as_ref()).next.set(next);
+    }
+
+    unsafe fn set_prev(node: NonNull<A::Value>, prev: Option<NonNull<A::Value>>) {
+        A::link(node.as_ref()).prev.set(prev);
+    }
+}
+
+/// A doubly linked list of values that live somewhere else, e.g. in an arena. The
+/// list threads itself through a `Link` embedded in every value, so it never allocates.
+///
+/// Each link remembers which list it belongs to, so values can only be removed from
+/// the list they are actually in, and a value can't be linked twice through one link.
+/// The values are borrowed for as long as the list lives, and dropping the list
+/// unlinks all of them again.
+pub struct IntrusiveList<'a, A: Adapter> {
+    head: Option<NonNull<A::Value>>,
+    tail: Option<NonNull<A::Value>>,
+    len: usize,
+    id: u64,
+    marker: PhantomData<&'a A::Value>,
+}
+
+impl<'a, A: Adapter> IntrusiveList<'a, A> {
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+            id: NEXT_LIST_ID.fetch_add(1, atomic::Ordering::Relaxed),
+            marker: PhantomData,
+        }
+    }
+
+    /// Panics if `value` is already linked into a list through this adapter's link
+    pub fn push_front(&mut self, value: &'a A::Value) {
+        let node = self.claim(value);
+
+        match self.head {
+            Some(head) => unsafe {
+                link_node_before::<Ops<A>>(head, node);
+            },
+            None => self.tail = Some(node),
+        }
+
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    /// Panics if `value` is already linked into a list through this adapter's link
+    pub fn push_back(&mut self, value: &'a A::Value) {
+        let node = self.claim(value);
+
+        match self.tail {
+            Some(tail) => unsafe {
+                link_node_after::<Ops<A>>(tail, node);
+            },
+            None => self.head = Some(node),
+        }
+
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    /// Links `value` right after `after`, which must be part of this list
+    pub fn insert_after(&mut self, after: &A::Value, value: &'a A::Value) {
+        if !self.contains(after) {
+            panic!("Value is not linked into this list.");
+        }
+
+        let after = NonNull::from(after);
+        let node = self.claim(value);
+        unsafe { link_node_after::<Ops<A>>(after, node) };
+
+        if self.tail == Some(after) {
+            self.tail = Some(node);
+        }
+        self.len += 1;
+    }
+
+    /// Links `value` right before `before`, which must be part of this list
+    pub fn insert_before(&mut self, before: &A::Value, value: &'a A::Value) {
+        if !self.contains(before) {
+            panic!("Value is not linked into this list.");
+        }
+
+        let before = NonNull::from(before);
+        let node = self.claim(value);
+        unsafe { link_node_before::<Ops<A>>(before, node) };
+
+        if self.head == Some(before) {
+            self.head = Some(node);
+        }
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<&'a A::Value> {
+        let head = self.head?;
+        return Some(unsafe { self.unlink(head) });
+    }
+
+    pub fn pop_back(&mut self) -> Option<&'a A::Value> {
+        let tail = self.tail?;
+        return Some(unsafe { self.unlink(tail) });
+    }
+
+    /// Unlinks `value` in O(1). Returns `false` if it is not part of this list.
+    pub fn remove(&mut self, value: &A::Value) -> bool {
+        if !self.contains(value) {
+            return false;
+        }
+
+        unsafe { self.unlink(NonNull::from(value)) };
+        return true;
+    }
+
+    /// Checks in O(1) whether `value` is linked into this list
+    pub fn contains(&self, value: &A::Value) -> bool {
+        return A::link(value).owner.get() == self.id;
+    }
+
+    pub fn front(&self) -> Option<&'a A::Value> {
+        self.head.map(|node| unsafe { &*node.as_ptr() })
+    }
+
+    pub fn back(&self) -> Option<&'a A::Value> {
+        self.tail.map(|node| unsafe { &*node.as_ptr() })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len == 0;
+    }
+
+    /// Unlinks every value so that they can be linked into other lists again
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn iter(&self) -> Iter<'_, A> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+// private helper functions
+impl<'a, A: Adapter> IntrusiveList<'a, A> {
+    /// Marks the link of `value` as owned by this list
+    fn claim(&self, value: &'a A::Value) -> NonNull<A::Value> {
+        let link = A::link(value);
+        if link.is_linked() {
+            panic!("Value is already linked into a list.");
+        }
+
+        link.owner.set(self.id);
+        return NonNull::from(value);
+    }
+
+    /// WARN: `node` must be linked into this list
+    unsafe fn unlink(&mut self, node: NonNull<A::Value>) -> &'a A::Value {
+        if self.head == Some(node) {
+            self.head = Ops::<A>::next(node);
+        }
+        if self.tail == Some(node) {
+            self.tail = Ops::<A>::prev(node);
+        }
+
+        unlink_node::<Ops<A>>(node);
+
+        // `unlink_node` leaves the pointers of an end node alone
+        let link = A::link(node.as_ref());
+        link.next.set(None);
+        link.prev.set(None);
+        link.owner.set(0);
+
+        self.len -= 1;
+        return &*node.as_ptr();
+    }
+}
+
+impl<'a, A: Adapter> Default for IntrusiveList<'a, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, A: Adapter> Drop for IntrusiveList<'a, A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<'a, A> Debug for IntrusiveList<'a, A>
+where
+    A: Adapter,
+    A::Value: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'s, 'a, A: Adapter> IntoIterator for &'s IntrusiveList<'a, A> {
+    type Item = &'s A::Value;
+
+    type IntoIter = Iter<'s, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+pub struct Iter<'a, A: Adapter> {
+    head: Option<NonNull<A::Value>>,
+    tail: Option<NonNull<A::Value>>,
+    len: usize,
+    marker: PhantomData<&'a A::Value>,
+}
+
+impl<'a, A: Adapter> Iterator for Iter<'a, A> {
+    type Item = &'a A::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        return self.head.map(|node| unsafe {
+            self.len -= 1;
+            self.head = Ops::<A>::next(node);
+            &*node.as_ptr()
+        });
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, A: Adapter> DoubleEndedIterator for Iter<'a, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        return self.tail.map(|node| unsafe {
+            self.len -= 1;
+            self.tail = Ops::<A>::prev(node);
+            &*node.as_ptr()
+        });
+    }
+}
+
+impl<A: Adapter> ExactSizeIterator for Iter<'_, A> {}
+impl<A: Adapter> FusedIterator for Iter<'_, A> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct Task {
+        id: u32,
+        ready: Link<Task>,
+        all: Link<Task>,
+    }
+
+    impl Task {
+        fn new(id: u32) -> Self {
+            Self {
+                id,
+                ..Default::default()
+            }
+        }
+    }
+
+    struct ReadyAdapter;
+
+    unsafe impl Adapter for ReadyAdapter {
+        type Value = Task;
+
+        fn link(value: &Task) -> &Link<Task> {
+            &value.ready
+        }
+    }
+
+    struct AllAdapter;
+
+    unsafe impl Adapter for AllAdapter {
+        type Value = Task;
+
+        fn link(value: &Task) -> &Link<Task> {
+            &value.all
+        }
+    }
+
+    fn ids<A: Adapter<Value = Task>>(list: &IntrusiveList<A>) -> Vec<u32> {
+        list.iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn can_push_and_pop() {
+        let tasks: Vec<Task> = (0..4).map(Task::new).collect();
+        let mut list: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+
+        list.push_back(&tasks[1]);
+        list.push_back(&tasks[2]);
+        list.push_front(&tasks[0]);
+        list.push_back(&tasks[3]);
+
+        assert_eq!(list.len(), 4);
+        assert_eq!(ids(&list), vec![0, 1, 2, 3]);
+        assert_eq!(
+            list.iter().rev().map(|t| t.id).collect::<Vec<_>>(),
+            vec![3, 2, 1, 0]
+        );
+        assert_eq!(list.front().map(|t| t.id), Some(0));
+        assert_eq!(list.back().map(|t| t.id), Some(3));
+
+        assert_eq!(list.pop_front().map(|t| t.id), Some(0));
+        assert_eq!(list.pop_back().map(|t| t.id), Some(3));
+        assert!(!tasks[0].ready.is_linked());
+        assert!(tasks[1].ready.is_linked());
+
+        assert_eq!(list.pop_back().map(|t| t.id), Some(2));
+        assert_eq!(list.pop_back().map(|t| t.id), Some(1));
+        assert_eq!(list.pop_back().map(|t| t.id), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn can_insert_around_values() {
+        let tasks: Vec<Task> = (0..5).map(Task::new).collect();
+        let mut list: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+
+        list.push_back(&tasks[2]);
+        list.insert_before(&tasks[2], &tasks[0]);
+        list.insert_after(&tasks[0], &tasks[1]);
+        list.insert_after(&tasks[2], &tasks[4]);
+        list.insert_before(&tasks[4], &tasks[3]);
+
+        assert_eq!(ids(&list), vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.front().map(|t| t.id), Some(0));
+        assert_eq!(list.back().map(|t| t.id), Some(4));
+    }
+
+    #[test]
+    fn can_remove_only_own_values() {
+        let tasks: Vec<Task> = (0..4).map(Task::new).collect();
+        let mut a: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+        let mut b: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+
+        a.push_back(&tasks[0]);
+        a.push_back(&tasks[1]);
+        a.push_back(&tasks[2]);
+        b.push_back(&tasks[3]);
+
+        assert!(!b.remove(&tasks[1]));
+        assert!(a.remove(&tasks[1]));
+        assert!(!a.remove(&tasks[1]));
+        assert!(a.remove(&tasks[2]));
+
+        assert_eq!(ids(&a), vec![0]);
+        assert_eq!(a.back().map(|t| t.id), Some(0));
+        assert_eq!(ids(&b), vec![3]);
+        assert!(b.contains(&tasks[3]));
+        assert!(!a.contains(&tasks[3]));
+    }
+
+    #[test]
+    fn can_link_into_several_lists_at_once() {
+        let tasks: Vec<Task> = (0..4).map(Task::new).collect();
+        let mut ready: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+        let mut all: IntrusiveList<AllAdapter> = IntrusiveList::new();
+
+        for task in tasks.iter() {
+            all.push_back(task);
+        }
+        ready.push_back(&tasks[3]);
+        ready.push_back(&tasks[1]);
+
+        all.remove(&tasks[3]);
+        assert_eq!(ids(&all), vec![0, 1, 2]);
+        assert_eq!(ids(&ready), vec![3, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Value is already linked into a list.")]
+    fn can_panic_when_linking_twice() {
+        let task = Task::new(0);
+        let mut a: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+        let mut b: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+
+        a.push_back(&task);
+        b.push_back(&task);
+    }
+
+    #[test]
+    #[should_panic(expected = "Value is not linked into this list.")]
+    fn can_panic_when_inserting_next_to_foreign_value() {
+        let tasks: Vec<Task> = (0..2).map(Task::new).collect();
+        let mut list: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+
+        list.insert_after(&tasks[0], &tasks[1]);
+    }
+
+    #[test]
+    fn can_unlink_everything_when_dropped() {
+        let tasks: Vec<Task> = (0..3).map(Task::new).collect();
+
+        {
+            let mut list: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+            tasks.iter().for_each(|task| list.push_back(task));
+            assert!(tasks.iter().all(|task| task.ready.is_linked()));
+        }
+
+        assert!(tasks.iter().all(|task| !task.ready.is_linked()));
+
+        let mut list: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+        tasks.iter().rev().for_each(|task| list.push_back(task));
+        assert_eq!(ids(&list), vec![2, 1, 0]);
+        assert_eq!(
+            format!("{:?}", list.front().unwrap().ready),
+            "Link { linked: true }"
+        );
+    }
+
+    /// These tests move values between lists and forget a list that still links values,
+    /// which covers the pointer juggling of `IntrusiveList`. They are meant to be run
+    /// under Miri as well with `cargo +nightly miri test intrusive_doubly_linked_list`.
+    mod miri {
+        use super::*;
+        use std::mem;
+
+        #[test]
+        fn can_move_values_between_lists() {
+            let tasks: Vec<Task> = (0..6).map(Task::new).collect();
+            let mut a: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+            let mut b: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+
+            tasks.iter().for_each(|task| a.push_back(task));
+            while let Some(task) = a.pop_back() {
+                if task.id % 2 == 0 {
+                    b.push_front(task);
+                }
+            }
+
+            assert!(a.is_empty());
+            assert_eq!(ids(&b), vec![0, 2, 4]);
+            b.remove(&tasks[2]);
+            b.insert_after(&tasks[0], &tasks[1]);
+            assert_eq!(ids(&b), vec![0, 1, 4]);
+        }
+
+        #[test]
+        fn can_forget_list_without_touching_values() {
+            let tasks: Vec<Task> = (0..3).map(Task::new).collect();
+            let mut list: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+            tasks.iter().for_each(|task| list.push_back(task));
+            mem::forget(list);
+
+            // The links stay claimed by the forgotten list, but no other list will
+            // ever follow them
+            let mut list: IntrusiveList<ReadyAdapter> = IntrusiveList::new();
+            assert!(!list.remove(&tasks[0]));
+            assert!(list.is_empty());
+            drop(tasks);
+        }
+    }
+}
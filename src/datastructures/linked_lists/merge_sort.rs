@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+
+/// Gives `merge_sort` access to the `next` links and values of the nodes of a list, no
+/// matter if they link to each other by pointer or by arena index
+pub(crate) trait SortLinks {
+    type Link: Copy;
+    type Value;
+
+    unsafe fn next(&self, node: Self::Link) -> Option<Self::Link>;
+    unsafe fn set_next(&mut self, node: Self::Link, next: Option<Self::Link>);
+    unsafe fn value(&self, node: Self::Link) -> &Self::Value;
+}
+
+/// Sorts the `len` nodes starting at `head` with a stable top-down merge sort and returns
+/// the new head and tail. Only the `next` links are rewritten and the tail ends up
+/// terminated. `len` must not be 0.
+///
+/// If `compare` panics the nodes are left in several terminated chains.
+pub(crate) unsafe fn merge_sort<L, F>(
+    links: &mut L,
+    head: L::Link,
+    len: usize,
+    compare: &mut F,
+) -> (L::Link, L::Link)
+where
+    L: SortLinks,
+    F: FnMut(&L::Value, &L::Value) -> Ordering,
+{
+    if len == 1 {
+        links.set_next(head, None);
+        return (head, head);
+    }
+
+    // Sorting the left half terminates it, so its end has to be found first
+    let mid = len / 2;
+    let mut right = head;
+    for _ in 0..mid {
+        right = links.next(right).unwrap();
+    }
+
+    let left = merge_sort(links, head, mid, compare);
+    let right = merge_sort(links, right, len - mid, compare);
+
+    return merge(links, left, right, compare);
+}
+
+/// Merges the sorted chains `left` and `right`, given by head and tail. Ties are taken
+/// from `left` first, which keeps the sort stable.
+unsafe fn merge<L, F>(
+    links: &mut L,
+    left: (L::Link, L::Link),
+    right: (L::Link, L::Link),
+    compare: &mut F,
+) -> (L::Link, L::Link)
+where
+    L: SortLinks,
+    F: FnMut(&L::Value, &L::Value) -> Ordering,
+{
+    let mut l = Some(left.0);
+    let mut r = Some(right.0);
+    let mut head = None;
+    let mut tail = None;
+
+    while let (Some(l_node), Some(r_node)) = (l, r) {
+        let node = if compare(links.value(l_node), links.value(r_node)) == Ordering::Greater {
+            r = links.next(r_node);
+            r_node
+        } else {
+            l = links.next(l_node);
+            l_node
+        };
+
+        match tail {
+            Some(tail) => links.set_next(tail, Some(node)),
+            None => head = Some(node),
+        }
+        tail = Some(node);
+    }
+
+    // One chain ran out, so the rest of the other one goes at the end as it is and its
+    // tail becomes the new tail. Both chains were non-empty, so a node was taken above.
+    let (rest, new_tail) = match l {
+        Some(l_node) => (l_node, left.1),
+        None => (r.unwrap(), right.1),
+    };
+    links.set_next(tail.unwrap(), Some(rest));
+
+    return (head.unwrap(), new_tail);
+}
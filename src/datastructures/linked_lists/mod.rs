@@ -1,3 +1,7 @@
+pub mod intrusive_doubly_linked_list;
+pub(crate) mod merge_sort;
+pub mod persistent_list;
 pub mod singly_linked_list;
+pub mod unrolled_linked_list;
 pub mod unsafe_doubly_linked_list;
 pub mod unsafe_singly_linked_list;
@@ -0,0 +1,310 @@
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::sync::Arc;
+
+/// An immutable singly linked list whose versions share their common tails.
+///
+/// `prepend` and `tail` never copy any nodes, they only return a new list pointing into
+/// the same chain, so keeping many versions around (e.g. an undo stack) is cheap.
+/// Nodes are reference counted with `Arc`, so versions can be shared between threads.
+pub struct PersistentList<T> {
+    head: Option<Arc<Node<T>>>,
+    len: usize,
+}
+
+struct Node<T> {
+    value: T,
+    next: Option<Arc<Node<T>>>,
+}
+
+impl<T> PersistentList<T> {
+    pub fn new() -> Self {
+        Self { head: None, len: 0 }
+    }
+
+    /// Builds a list with the items in the same order as they are yielded
+    pub fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let values: Vec<T> = iter.into_iter().collect();
+        let mut list = Self::new();
+
+        for value in values.into_iter().rev() {
+            list = list.prepend(value);
+        }
+
+        return list;
+    }
+
+    /// Returns a new list with `value` in front of this one in O(1). Both lists share
+    /// all of the nodes of this list.
+    pub fn prepend(&self, value: T) -> Self {
+        let new_node = Node {
+            value,
+            next: self.head.clone(),
+        };
+
+        Self {
+            head: Some(Arc::new(new_node)),
+            len: self.len + 1,
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    /// Returns the list without its first element in O(1), sharing the remaining nodes.
+    /// The tail of an empty list is empty.
+    pub fn tail(&self) -> Self {
+        match &self.head {
+            Some(node) => Self {
+                head: node.next.clone(),
+                len: self.len - 1,
+            },
+            None => Self::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len == 0;
+    }
+
+    /// Returns `true` if both lists start at the very same node, in which case they are
+    /// guaranteed to be equal without comparing any values
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next_node: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Clone> PersistentList<T> {
+    /// Returns a reversed copy of the list. Nothing can be shared because every node
+    /// gets a new successor.
+    pub fn reverse(&self) -> Self {
+        let mut list = Self::new();
+        for value in self.iter() {
+            list = list.prepend(value.clone());
+        }
+
+        return list;
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for PersistentList<T> {
+    /// Clones the list in O(1) by sharing all of its nodes
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Drop for PersistentList<T> {
+    fn drop(&mut self) {
+        // Dropping the nodes recursively could overflow the stack for long lists, so
+        // unlink them one at a time until reaching a node that another list still uses.
+        // Unlike `Arc::try_unwrap`, `Arc::into_inner` hands the node to exactly one of
+        // several threads releasing it at the same time, so the chain is never left to a
+        // recursive drop.
+        let mut curr_node = self.head.take();
+        while let Some(node) = curr_node {
+            match Arc::into_inner(node) {
+                Some(mut node) => curr_node = node.next.take(),
+                None => break,
+            }
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.len == other.len && (self.ptr_eq(other) || self.iter().eq(other.iter()));
+    }
+}
+
+impl<T: Eq> Eq for PersistentList<T> {}
+
+impl<T: Debug> Debug for PersistentList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+pub struct Iter<'a, T> {
+    next_node: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.next_node.map(|node| {
+            self.next_node = node.next.as_deref();
+            self.len -= 1;
+            return &node.value;
+        });
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[test]
+    fn can_prepend_and_get_head() {
+        let list = PersistentList::new();
+        assert_eq!(list.head(), None);
+        assert!(list.is_empty());
+
+        let list = list.prepend(3).prepend(2).prepend(1);
+        assert_eq!(list.head(), Some(&1));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    fn can_get_tail() {
+        let list = PersistentList::from_iter(vec![1, 2, 3]);
+
+        let tail = list.tail();
+        assert_eq!(tail.head(), Some(&2));
+        assert_eq!(tail.len(), 2);
+
+        let tail = tail.tail().tail();
+        assert!(tail.is_empty());
+        assert!(tail.tail().is_empty());
+
+        // The original list is untouched
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn can_share_structure() {
+        let base = PersistentList::from_iter(vec![2, 3]);
+        let a = base.prepend(1);
+        let b = base.prepend(10);
+
+        assert!(a.tail().ptr_eq(&base));
+        assert!(b.tail().ptr_eq(&a.tail()));
+        assert!(!a.ptr_eq(&b));
+        assert!(a.clone().ptr_eq(&a));
+
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![10, 2, 3]);
+
+        // Dropping one version keeps the shared nodes alive for the others
+        drop(base);
+        drop(a);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![10, 2, 3]);
+    }
+
+    #[test]
+    fn can_compare_and_reverse() {
+        let list = PersistentList::from_iter(vec![1, 2, 3]);
+
+        assert_eq!(list, PersistentList::from_iter(vec![1, 2, 3]));
+        assert_ne!(list, list.tail());
+        assert_eq!(list.reverse(), PersistentList::from_iter(vec![3, 2, 1]));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn can_drop_long_lists() {
+        let mut list = PersistentList::new();
+        for i in 0..1_000_000 {
+            list = list.prepend(i);
+        }
+
+        let shared = list.tail();
+        drop(list);
+        assert_eq!(shared.len(), 999_999);
+        drop(shared);
+    }
+
+    #[test]
+    fn can_drop_long_shared_lists_from_threads() {
+        // The race is only lost when the last two owners let go at the same moment, so
+        // give it a few rounds
+        for _ in 0..5 {
+            let mut list = PersistentList::new();
+            for i in 0..1_000_000 {
+                list = list.prepend(i);
+            }
+
+            let barrier = Arc::new(Barrier::new(4));
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    let list = list.clone();
+                    let barrier = barrier.clone();
+                    thread::spawn(move || {
+                        barrier.wait();
+                        drop(list);
+                    })
+                })
+                .collect();
+
+            drop(list);
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn can_share_between_threads() {
+        let list = PersistentList::from_iter(vec![1, 2, 3]);
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let list = list.clone();
+                thread::spawn(move || list.prepend(i).iter().sum::<i32>())
+            })
+            .collect();
+
+        let sums: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(sums, vec![6, 7, 8, 9]);
+    }
+}
@@ -1,20 +1,36 @@
+use std::cmp::Ordering;
 use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem;
 
+use crate::datastructures::arena::Arena;
+use crate::datastructures::linked_lists::merge_sort::{merge_sort, SortLinks};
+
+/// A singly linked list whose nodes live in an arena and link to each other by index.
+/// Tracking the tail by index keeps `push_back` and `back` O(1) without any raw pointers.
 #[derive(Clone)]
 pub struct SinglyLinkedList<T> {
-    head: Option<Box<Node<T>>>,
+    nodes: Arena<Node<T>>,
+    head: Option<usize>,
+    tail: Option<usize>,
     len: usize,
 }
 
 #[derive(Debug, Clone)]
 struct Node<T> {
     value: T,
-    next: Option<Box<Node<T>>>,
+    next: Option<usize>,
 }
 
 impl<T> SinglyLinkedList<T> {
     pub fn new() -> Self {
-        Self { head: None, len: 0 }
+        Self {
+            nodes: Arena::new(),
+            head: None,
+            tail: None,
+            len: 0,
+        }
     }
 
     pub fn from_iter<I>(iter: I) -> Self
@@ -41,119 +57,113 @@ impl<T> SinglyLinkedList<T> {
         } else if index == self.len {
             self.push_back(value);
         } else {
-            let mut i = 0;
-            let mut curr_node = &mut self.head;
-            while let Some(node) = curr_node {
-                if i == index - 1 {
-                    let new_node = Node {
-                        value,
-                        next: node.next.take(),
-                    };
-                    node.next = Some(Box::new(new_node));
-                    break;
-                }
-
-                curr_node = &mut node.next;
-                i += 1;
-            }
+            let prev = self.node_index(index - 1).unwrap();
+            let new_node = self.allocate_node(value, self.node(prev).next);
+            self.node_mut(prev).next = Some(new_node);
 
             self.len += 1;
         }
     }
 
     pub fn push_front(&mut self, value: T) {
-        let mut new_node = Node { value, next: None };
-        match &mut self.head {
-            Some(_) => {
-                new_node.next = self.head.take();
-                self.head = Some(Box::new(new_node));
-            }
-            None => self.head = Some(Box::new(new_node)),
+        let new_node = self.allocate_node(value, self.head);
+        self.head = Some(new_node);
+        if self.tail.is_none() {
+            self.tail = Some(new_node);
         }
 
         self.len += 1;
     }
 
     pub fn push_back(&mut self, value: T) {
-        let new_node = Some(Box::new(Node { value, next: None }));
-        if self.head.is_some() {
-            let mut curr_node = &mut self.head;
-            while let Some(node) = curr_node {
-                if node.next.is_none() {
-                    node.next = new_node;
-                    break;
-                }
-                curr_node = &mut node.next;
-            }
-        } else {
-            self.head = new_node;
+        let new_node = self.allocate_node(value, None);
+        match self.tail {
+            Some(tail) => self.node_mut(tail).next = Some(new_node),
+            None => self.head = Some(new_node),
         }
+        self.tail = Some(new_node);
 
         self.len += 1;
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
-        self.head.take().map(|node| {
-            let next = node.next;
-            self.head = next;
-            node.value
-        })
+        let head = self.head?;
+        let node = self.deallocate_node(head);
+
+        self.head = node.next;
+        if self.head.is_none() {
+            self.tail = None;
+        }
+
+        self.len -= 1;
+        return Some(node.value);
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
-        // if self.len == 0 {
-        //     return None;
-        // } else if self.len == 1 {
-        //     return self.pop_front();
-        // } else {
-        //     let mut curr_node = self.head;
-        //     let mut i = 0;
-        //     while let Some(node) = curr_node {
-        //         if i == self.len() - 1 {
-        //             let mut curr_node = curr_node.expect("Node should not be None");
-        //             let back_node = curr_node.next.expect("Node should not be None");
-        //             curr_node.next = back_node.next;
-        //
-        //             return Some(back_node.value);
-        //         }
-        //
-        //         curr_node = node.next;
-        //         i += 1;
-        //     }
-        //
-        //     return None;
-        //
-        // }
-        todo!("Not sure how to implement");
+        // Take care of cases where `self.len - 2` will cause an integer underflow
+        if self.len <= 1 {
+            return self.pop_front();
+        }
+
+        let second_last = self.node_index(self.len - 2)?;
+        let back_node = self.node_mut(second_last).next.take()?;
+        self.tail = Some(second_last);
+
+        self.len -= 1;
+        return Some(self.deallocate_node(back_node).value);
     }
 
     pub fn get(&self, index: usize) -> Option<&T> {
-        let mut curr_node = &self.head;
+        let node = self.node_index(index)?;
+        return Some(&self.node(node).value);
+    }
 
-        if index > 0 {
-            let mut i = 0;
-            while let Some(node) = curr_node {
-                if i == index {
-                    break;
-                }
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let node = self.node_index(index)?;
+        return Some(&mut self.node_mut(node).value);
+    }
 
-                curr_node = &node.next;
-                i += 1;
-            }
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
         }
 
-        match curr_node.as_deref() {
-            Some(node) => Some(&node.value),
-            None => None,
+        if index == 0 {
+            return self.pop_front();
         }
+
+        let prev = self.node_index(index - 1)?;
+        let target = self.node(prev).next?;
+        let target_node = self.deallocate_node(target);
+
+        self.node_mut(prev).next = target_node.next;
+        if self.tail == Some(target) {
+            self.tail = Some(prev);
+        }
+
+        self.len -= 1;
+        return Some(target_node.value);
     }
 
-    pub fn get_mut(&self, _index: usize) -> Option<&mut T> {
-        todo!("Don't know if possible")
+    /// Reverses the order of the nodes in place without moving any values
+    pub fn reverse(&mut self) {
+        let mut prev = None;
+        let mut curr_node = self.head;
+
+        while let Some(node) = curr_node {
+            curr_node = self.node(node).next;
+            self.node_mut(node).next = prev;
+            prev = Some(node);
+        }
+
+        self.tail = self.head;
+        self.head = prev;
     }
 
     pub fn clear(&mut self) {
-        self.head.take();
+        self.nodes.clear();
+        self.head = None;
+        self.tail = None;
         self.len = 0;
     }
 
@@ -162,33 +172,28 @@ impl<T> SinglyLinkedList<T> {
     }
 
     pub fn front(&self) -> Option<&T> {
-        self.get(0)
+        self.head.map(|node| &self.node(node).value)
     }
 
     pub fn front_mut(&mut self) -> Option<&mut T> {
-        self.get_mut(0)
+        let head = self.head?;
+        return Some(&mut self.node_mut(head).value);
     }
 
     pub fn back(&self) -> Option<&T> {
-        if self.len() >= 1 {
-            self.get(self.len() - 1)
-        } else {
-            None
-        }
+        self.tail.map(|node| &self.node(node).value)
     }
 
     pub fn back_mut(&mut self) -> Option<&mut T> {
-        if self.len() >= 1 {
-            self.get_mut(self.len() - 1)
-        } else {
-            None
-        }
+        let tail = self.tail?;
+        return Some(&mut self.node_mut(tail).value);
     }
 
-    pub fn to_vec(self) -> Vec<T> {
+    pub fn to_vec(mut self) -> Vec<T> {
         let mut vec = Vec::with_capacity(self.len);
         let mut curr_node = self.head;
         while let Some(node) = curr_node {
+            let node = self.nodes.free(node);
             vec.push(node.value);
 
             curr_node = node.next;
@@ -196,17 +201,152 @@ impl<T> SinglyLinkedList<T> {
 
         return vec;
     }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            nodes: &self.nodes,
+            next_node: self.head,
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            nodes: self.nodes.slots_mut().as_mut_ptr(),
+            next_node: self.head,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> SinglyLinkedList<T> {
+    /// Sorts the list with a stable merge sort in O(n log n). Only the links between the
+    /// nodes change, the values are never moved or cloned.
+    ///
+    /// If `compare` panics the list keeps all of its values, but in an unspecified order.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len <= 1 {
+            return;
+        }
+
+        // The half merged runs are scattered across broken chains, but every occupied
+        // arena slot is still a node of the list, so linking them in slot order makes the
+        // list whole again
+        struct PanicGuard<'a, T>(&'a mut SinglyLinkedList<T>);
+
+        impl<T> Drop for PanicGuard<'_, T> {
+            fn drop(&mut self) {
+                let list = &mut *self.0;
+                let mut prev: Option<usize> = None;
+
+                for slot in 0..list.nodes.capacity() {
+                    if let Some(node) = &mut list.nodes.slots_mut()[slot] {
+                        node.next = None;
+                        match prev {
+                            Some(prev) => list.node_mut(prev).next = Some(slot),
+                            None => list.head = Some(slot),
+                        }
+                        prev = Some(slot);
+                    }
+                }
+
+                list.tail = prev;
+            }
+        }
+
+        let (head, len) = (self.head.unwrap(), self.len);
+        let guard = PanicGuard(self);
+        // The arena checks every index it is given, so sorting through it is safe
+        let (head, tail) = unsafe { merge_sort(&mut guard.0.nodes, head, len, &mut compare) };
+        mem::forget(guard);
+
+        self.head = Some(head);
+        self.tail = Some(tail);
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+}
+
+impl<T: Ord> SinglyLinkedList<T> {
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp);
+    }
+}
+
+// private helper functions
+impl<T> SinglyLinkedList<T> {
+    fn node(&self, node: usize) -> &Node<T> {
+        self.nodes.get(node)
+    }
+
+    fn node_mut(&mut self, node: usize) -> &mut Node<T> {
+        self.nodes.get_mut(node)
+    }
+
+    /// Returns the arena slot of the node at `index`. The last node is found in O(1).
+    fn node_index(&self, index: usize) -> Option<usize> {
+        if index >= self.len {
+            return None;
+        }
+
+        if index == self.len - 1 {
+            return self.tail;
+        }
+
+        let mut curr_node = self.head;
+        for _ in 0..index {
+            curr_node = self.node(curr_node?).next;
+        }
+
+        return curr_node;
+    }
+
+    fn allocate_node(&mut self, value: T, next: Option<usize>) -> usize {
+        return self.nodes.alloc(Node { value, next });
+    }
+
+    fn deallocate_node(&mut self, node: usize) -> Node<T> {
+        return self.nodes.free(node);
+    }
+}
+
+impl<T> SortLinks for Arena<Node<T>> {
+    type Link = usize;
+    type Value = T;
+
+    unsafe fn next(&self, node: usize) -> Option<usize> {
+        self.get(node).next
+    }
+
+    unsafe fn set_next(&mut self, node: usize, next: Option<usize>) {
+        self.get_mut(node).next = next;
+    }
+
+    unsafe fn value(&self, node: usize) -> &T {
+        &self.get(node).value
+    }
 }
 
 impl<T: PartialEq> SinglyLinkedList<T> {
-    pub fn contains(&self, value: T) -> bool {
-        let mut curr_node = &self.head;
+    pub fn contains(&self, value: &T) -> bool {
+        let mut curr_node = self.head;
         while let Some(node) = curr_node {
-            if node.value == value {
+            let node = self.node(node);
+            if node.value == *value {
                 return true;
             }
 
-            curr_node = &node.next;
+            curr_node = node.next;
         }
 
         return false;
@@ -216,19 +356,124 @@ impl<T: PartialEq> SinglyLinkedList<T> {
 impl<T: Debug> Debug for SinglyLinkedList<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("[")?;
-        let mut curr_node = &self.head;
+        let mut curr_node = self.head;
         while let Some(node) = curr_node {
+            let node = self.node(node);
             match &node.next {
                 Some(_) => f.write_fmt(format_args!("{:?}->", node.value))?,
                 None => f.write_fmt(format_args!("{:?}", node.value))?,
             }
-            curr_node = &node.next;
+            curr_node = node.next;
         }
 
         f.write_str("]")
     }
 }
 
+impl<T> IntoIterator for SinglyLinkedList<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return IntoIter { list: self };
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SinglyLinkedList<T> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SinglyLinkedList<T> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter_mut();
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    list: SinglyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.list.pop_front();
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+
+pub struct Iter<'a, T> {
+    nodes: &'a Arena<Node<T>>,
+    next_node: Option<usize>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.get(self.next_node?);
+        self.next_node = node.next;
+        self.len -= 1;
+
+        return Some(&node.value);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Follows the `next` indices like `Iter`, but through a raw pointer to the arena. Every
+/// slot of the list is visited exactly once, so the returned references never alias.
+pub struct IterMut<'a, T> {
+    nodes: *mut Option<Node<T>>,
+    next_node: Option<usize>,
+    len: usize,
+    marker: PhantomData<&'a mut [Option<Node<T>>]>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // `next_node` is a live slot of the arena, which stays mutably borrowed for 'a
+        let node = unsafe { (*self.nodes.add(self.next_node?)).as_mut().unwrap() };
+        self.next_node = node.next;
+        self.len -= 1;
+
+        return Some(&mut node.value);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> FusedIterator for IterMut<'_, T> {}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -268,31 +513,32 @@ mod test {
         }
     }
 
-    // #[test]
-    // fn can_get_and_mutate_value_by_index() {
-    //     let vec = vec![1, 2, 3, 4, 5, 6, 7];
-    //     let linked_list = SinglyLinkedList::from_iter(vec.clone());
-    //
-    //     for idx in 0..linked_list.len()  {
-    //         *linked_list.get_mut(idx).unwrap() += 1;
-    //     }
-    //
-    //     for (idx, n) in vec.iter().enumerate() {
-    //         assert_eq!(linked_list.get(idx), Some(&(n + 1)));
-    //     }
-    // }
-
-    // #[test]
-    // fn can_remove() {
-    //     let mut linked_list = SinglyLinkedList::from_iter(vec![1, 2, 3, 4]);
-    //
-    //     assert_eq!(linked_list.remove(linked_list.len() - 1), Some(4));
-    //     assert_eq!(linked_list.remove(linked_list.len() - 1), Some(3));
-    //     assert_eq!(linked_list.remove(1), Some(2));
-    //     assert_eq!(linked_list.remove(0), Some(1));
-    //     assert_eq!(linked_list.remove(0), None);
-    //     assert_eq!(linked_list.len(), 0);
-    // }
+    #[test]
+    fn can_get_and_mutate_value_by_index() {
+        let vec = vec![1, 2, 3, 4, 5, 6, 7];
+        let mut linked_list = SinglyLinkedList::from_iter(vec.clone());
+
+        for idx in 0..linked_list.len() {
+            *linked_list.get_mut(idx).unwrap() += 1;
+        }
+
+        for (idx, n) in vec.iter().enumerate() {
+            assert_eq!(linked_list.get(idx), Some(&(n + 1)));
+        }
+        assert_eq!(linked_list.get_mut(vec.len()), None);
+    }
+
+    #[test]
+    fn can_remove() {
+        let mut linked_list = SinglyLinkedList::from_iter(vec![1, 2, 3, 4]);
+
+        assert_eq!(linked_list.remove(linked_list.len() - 1), Some(4));
+        assert_eq!(linked_list.remove(linked_list.len() - 1), Some(3));
+        assert_eq!(linked_list.remove(1), Some(2));
+        assert_eq!(linked_list.remove(0), Some(1));
+        assert_eq!(linked_list.remove(0), None);
+        assert_eq!(linked_list.len(), 0);
+    }
 
     #[test]
     fn can_insert() {
@@ -329,26 +575,26 @@ mod test {
         assert_eq!(linked_list.pop_front(), None);
     }
 
-    // #[test]
-    // fn can_get_and_pop_back() {
-    //     let mut linked_list = SinglyLinkedList::from_iter(vec![1, 2, 3]);
-    //
-    //     assert_eq!(linked_list.back(), Some(&3));
-    //     assert_eq!(linked_list.pop_back(), Some(3));
-    //     println!("3, {}", linked_list.len());
-    //
-    //     assert_eq!(linked_list.back(), Some(&2));
-    //     assert_eq!(linked_list.pop_back(), Some(2));
-    //     println!("3, {}", linked_list.len());
-    //
-    //     assert_eq!(linked_list.back(), Some(&1));
-    //     assert_eq!(linked_list.pop_back(), Some(1));
-    //     println!("3, {}", linked_list.len());
-    //
-    //     assert_eq!(linked_list.back(), None);
-    //     assert_eq!(linked_list.pop_back(), None);
-    //     println!("3, {}", linked_list.len());
-    // }
+    #[test]
+    fn can_get_and_pop_back() {
+        let mut linked_list = SinglyLinkedList::from_iter(vec![1, 2, 3]);
+
+        assert_eq!(linked_list.back(), Some(&3));
+        assert_eq!(linked_list.pop_back(), Some(3));
+        assert_eq!(linked_list.len(), 2);
+
+        assert_eq!(linked_list.back(), Some(&2));
+        assert_eq!(linked_list.pop_back(), Some(2));
+        assert_eq!(linked_list.len(), 1);
+
+        assert_eq!(linked_list.back(), Some(&1));
+        assert_eq!(linked_list.pop_back(), Some(1));
+        assert_eq!(linked_list.len(), 0);
+
+        assert_eq!(linked_list.back(), None);
+        assert_eq!(linked_list.pop_back(), None);
+        assert_eq!(linked_list.len(), 0);
+    }
 
     #[test]
     fn can_clear() {
@@ -360,4 +606,201 @@ mod test {
         assert_eq!(linked_list.back(), None);
         assert_eq!(linked_list.len(), 0);
     }
+
+    #[test]
+    fn can_check_if_contains() {
+        let linked_list = SinglyLinkedList::from_iter(vec![1, 2, 3]);
+
+        assert!(linked_list.contains(&1));
+        assert!(linked_list.contains(&3));
+        assert!(!linked_list.contains(&4));
+    }
+
+    #[test]
+    fn can_iterate() {
+        let vec = vec![1, 2, 3, 4, 5];
+        let mut linked_list = SinglyLinkedList::from_iter(vec.clone());
+
+        let mut iter = linked_list.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &3, &4, &5]);
+
+        for n in linked_list.iter_mut() {
+            *n *= 10;
+        }
+        for n in &mut linked_list {
+            *n += 1;
+        }
+        assert_eq!(
+            (&linked_list).into_iter().copied().collect::<Vec<_>>(),
+            vec![11, 21, 31, 41, 51]
+        );
+
+        let mut into_iter = linked_list.into_iter();
+        assert_eq!(into_iter.len(), 5);
+        assert_eq!(into_iter.next(), Some(11));
+        assert_eq!(into_iter.collect::<Vec<_>>(), vec![21, 31, 41, 51]);
+    }
+
+    #[test]
+    fn can_iter_mut_lazily_in_list_order() {
+        // Arena slots out of list order, with a free slot in between
+        let mut linked_list = SinglyLinkedList::from_iter(vec![3, 4, 5]);
+        linked_list.remove(1);
+        linked_list.push_front(2);
+        linked_list.push_front(1);
+        linked_list.reverse();
+
+        let mut iter = linked_list.iter_mut();
+        assert_eq!(iter.len(), 4);
+        *iter.next().unwrap() *= 10;
+        assert_eq!(iter.len(), 3);
+        let rest: Vec<_> = iter.collect();
+        for n in rest {
+            *n += 1;
+        }
+
+        assert_eq!(linked_list.to_vec(), vec![50, 4, 3, 2]);
+    }
+
+    #[test]
+    fn can_reverse() {
+        let mut linked_list = SinglyLinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+        linked_list.reverse();
+
+        assert_eq!(linked_list.len(), 5);
+        assert_eq!(linked_list.front(), Some(&5));
+        assert_eq!(linked_list.back(), Some(&1));
+        assert_eq!(linked_list.to_vec(), vec![5, 4, 3, 2, 1]);
+
+        let mut linked_list: SinglyLinkedList<i32> = SinglyLinkedList::new();
+        linked_list.reverse();
+        assert_eq!(linked_list.to_vec(), vec![]);
+
+        let mut linked_list = SinglyLinkedList::from_iter(vec![1]);
+        linked_list.reverse();
+        assert_eq!(linked_list.to_vec(), vec![1]);
+    }
+
+    #[test]
+    fn can_track_tail() {
+        let mut linked_list = SinglyLinkedList::new();
+        linked_list.push_front(2);
+        assert_eq!(linked_list.back(), Some(&2));
+
+        linked_list.push_back(3);
+        linked_list.push_front(1);
+        assert_eq!(linked_list.back(), Some(&3));
+
+        *linked_list.back_mut().unwrap() = 4;
+        linked_list.insert(3, 5);
+        assert_eq!(linked_list.back(), Some(&5));
+
+        // Removing the last node moves the tail back
+        assert_eq!(linked_list.remove(3), Some(5));
+        assert_eq!(linked_list.back(), Some(&4));
+
+        assert_eq!(linked_list.pop_back(), Some(4));
+        assert_eq!(linked_list.back(), Some(&2));
+
+        linked_list.reverse();
+        assert_eq!(linked_list.back(), Some(&1));
+
+        linked_list.push_back(0);
+        assert_eq!(linked_list.to_vec(), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn can_reuse_freed_nodes() {
+        let mut linked_list = SinglyLinkedList::from_iter(vec![1, 2, 3, 4]);
+
+        linked_list.remove(1);
+        linked_list.pop_front();
+        linked_list.push_back(5);
+        linked_list.push_front(0);
+        assert_eq!(linked_list.nodes.capacity(), 4);
+        assert_eq!(linked_list.len(), 4);
+
+        // Once every node is gone the arena is released
+        while linked_list.pop_back().is_some() {}
+        assert_eq!(linked_list.nodes.capacity(), 0);
+        assert_eq!(linked_list.front(), None);
+        assert_eq!(linked_list.back(), None);
+
+        linked_list.push_back(1);
+        assert_eq!(linked_list.to_vec(), vec![1]);
+    }
+
+    #[test]
+    fn can_sort() {
+        let mut linked_list = SinglyLinkedList::from_iter(vec![5, 1, 4, 2, 3, 2, 0]);
+        linked_list.sort();
+
+        assert_eq!(linked_list.len(), 7);
+        assert_eq!(linked_list.back(), Some(&5));
+        linked_list.push_back(6);
+        assert_eq!(linked_list.to_vec(), vec![0, 1, 2, 2, 3, 4, 5, 6]);
+
+        let mut linked_list: SinglyLinkedList<i32> = SinglyLinkedList::new();
+        linked_list.sort();
+        assert_eq!(linked_list.len(), 0);
+
+        let mut linked_list = SinglyLinkedList::from_iter(vec![1]);
+        linked_list.sort();
+        assert_eq!(linked_list.to_vec(), vec![1]);
+    }
+
+    #[test]
+    fn can_sort_by_and_sort_by_key_stably() {
+        let mut linked_list = SinglyLinkedList::from_iter(vec![1, 2, 3, 4, 5, 6, 7]);
+        linked_list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(linked_list.back(), Some(&1));
+        assert_eq!(
+            linked_list.iter().copied().collect::<Vec<_>>(),
+            vec![7, 6, 5, 4, 3, 2, 1]
+        );
+
+        let pairs = vec![(2, 'a'), (1, 'b'), (2, 'c'), (0, 'd'), (1, 'e'), (2, 'f')];
+        let mut linked_list = SinglyLinkedList::from_iter(pairs);
+        linked_list.sort_by_key(|pair| pair.0);
+
+        assert_eq!(linked_list.back(), Some(&(2, 'f')));
+        assert_eq!(
+            linked_list.to_vec(),
+            vec![(0, 'd'), (1, 'b'), (1, 'e'), (2, 'a'), (2, 'c'), (2, 'f')]
+        );
+    }
+
+    #[test]
+    fn can_keep_values_when_compare_panics() {
+        // Leave a free slot in the arena so relinking has to skip it
+        let mut linked_list = SinglyLinkedList::from_iter(vec![4, 2, 9, 5, 1, 3]);
+        linked_list.remove(2);
+
+        let mut comparisons = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            linked_list.sort_by(|a, b| {
+                comparisons += 1;
+                if comparisons == 4 {
+                    panic!("comparison failed");
+                }
+                a.cmp(b)
+            });
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(linked_list.len(), 5);
+        assert_eq!(linked_list.iter().len(), 5);
+
+        let mut values: Vec<_> = linked_list.iter().copied().collect();
+        assert_eq!(linked_list.back(), values.last());
+        values.sort();
+        assert_eq!(values, vec![1, 2, 3, 4, 5]);
+
+        linked_list.push_back(6);
+        linked_list.sort();
+        assert_eq!(linked_list.to_vec(), vec![1, 2, 3, 4, 5, 6]);
+    }
 }
@@ -0,0 +1,696 @@
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::slice;
+
+use super::unsafe_doubly_linked_list::{self, DoublyLinkedList};
+use crate::datastructures::lists::small_array_list::SmallArrayList;
+
+/// A doubly linked list of chunks that each store up to `N` elements inline, so that
+/// neighbouring elements usually share a cache line and the list needs one allocation
+/// per chunk rather than one per element.
+///
+/// Full chunks are split in half when inserting into them, and a chunk that drops below
+/// half full is merged with or refilled from the next chunk. That keeps every chunk but
+/// the last at least half full.
+pub struct UnrolledLinkedList<T, const N: usize = 16> {
+    chunks: DoublyLinkedList<SmallArrayList<T, N>>,
+    len: usize,
+}
+
+impl<T, const N: usize> UnrolledLinkedList<T, N> {
+    pub fn new() -> Self {
+        const { assert!(N >= 2, "Chunks must be able to hold at least 2 elements") };
+
+        Self {
+            chunks: DoublyLinkedList::new(),
+            len: 0,
+        }
+    }
+
+    pub fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut list = Self::new();
+        for value in iter {
+            list.push_back(value);
+        }
+
+        return list;
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        if index > self.len {
+            panic!("Index out of bounds.");
+        }
+
+        self.cursor_at_mut(index).insert_before(value);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        return self.cursor_at_mut(index).remove_current();
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        self.cursor_ghost_mut().insert_after(value);
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.cursor_ghost_mut().insert_before(value);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        return self.cursor_front_mut().remove_current();
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        return self.cursor_back_mut().remove_current();
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let mut remaining = index;
+        for chunk in self.chunks.iter() {
+            if remaining < chunk.len() {
+                return chunk.get(remaining);
+            }
+            remaining -= chunk.len();
+        }
+
+        return None;
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let mut remaining = index;
+        for chunk in self.chunks.iter_mut() {
+            if remaining < chunk.len() {
+                return chunk.get_mut(remaining);
+            }
+            remaining -= chunk.len();
+        }
+
+        return None;
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.chunks
+            .front()
+            .and_then(|chunk| chunk.as_slice().first())
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.chunks.back().and_then(|chunk| chunk.as_slice().last())
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len == 0;
+    }
+
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.len = 0;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            chunks: self.chunks.iter(),
+            values: [].iter(),
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, N> {
+        IterMut {
+            chunks: self.chunks.iter_mut(),
+            values: [].iter_mut(),
+            len: self.len,
+        }
+    }
+
+    /// Returns a cursor pointing at the front element, or at the "ghost" element
+    /// if the list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, N> {
+        CursorMut {
+            index: 0,
+            offset: 0,
+            chunks: self.chunks.cursor_front_mut(),
+            len: &mut self.len,
+        }
+    }
+
+    /// Returns a cursor pointing at the back element, or at the "ghost" element
+    /// if the list is empty
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, N> {
+        let offset = self.chunks.back().map_or(0, |chunk| chunk.len() - 1);
+
+        CursorMut {
+            index: self.len.saturating_sub(1),
+            offset,
+            chunks: self.chunks.cursor_back_mut(),
+            len: &mut self.len,
+        }
+    }
+}
+
+// private helper functions
+impl<T, const N: usize> UnrolledLinkedList<T, N> {
+    fn cursor_ghost_mut(&mut self) -> CursorMut<'_, T, N> {
+        let mut cursor = self.cursor_front_mut();
+        if cursor.index().is_some() {
+            cursor.move_prev();
+        }
+
+        return cursor;
+    }
+
+    /// Returns a cursor at `index`, skipping whole chunks at a time. An `index` of
+    /// `self.len` gives the ghost element.
+    fn cursor_at_mut(&mut self, index: usize) -> CursorMut<'_, T, N> {
+        let mut cursor = self.cursor_front_mut();
+
+        let mut remaining = index;
+        while let Some(chunk) = cursor.chunks.current() {
+            if remaining < chunk.len() {
+                break;
+            }
+            remaining -= chunk.len();
+            cursor.chunks.move_next();
+        }
+
+        cursor.offset = remaining;
+        cursor.index = index;
+        return cursor;
+    }
+}
+
+impl<T, const N: usize> Default for UnrolledLinkedList<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for UnrolledLinkedList<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a UnrolledLinkedList<T, N> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut UnrolledLinkedList<T, N> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter_mut();
+    }
+}
+
+pub struct Iter<'a, T, const N: usize> {
+    chunks: unsafe_doubly_linked_list::Iter<'a, SmallArrayList<T, N>>,
+    values: slice::Iter<'a, T>,
+    len: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.values.next() {
+                self.len -= 1;
+                return Some(value);
+            }
+
+            self.values = self.chunks.next()?.as_slice().iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Iter<'_, T, N> {}
+impl<T, const N: usize> FusedIterator for Iter<'_, T, N> {}
+
+pub struct IterMut<'a, T, const N: usize> {
+    chunks: unsafe_doubly_linked_list::IterMut<'a, SmallArrayList<T, N>>,
+    values: slice::IterMut<'a, T>,
+    len: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for IterMut<'a, T, N> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.values.next() {
+                self.len -= 1;
+                return Some(value);
+            }
+
+            self.values = self.chunks.next()?.as_mut_slice().iter_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IterMut<'_, T, N> {}
+impl<T, const N: usize> FusedIterator for IterMut<'_, T, N> {}
+
+/// A cursor over an `UnrolledLinkedList` that can insert and remove elements at its
+/// position. Moving and editing only ever touches the current chunk and its neighbour.
+///
+/// Besides the elements of the list the cursor can also point at a "ghost" element
+/// that sits between the back and the front of the list, which is where it ends up
+/// after moving past either end.
+pub struct CursorMut<'a, T, const N: usize> {
+    index: usize,
+    /// Position of the current element within the current chunk
+    offset: usize,
+    chunks: unsafe_doubly_linked_list::CursorMut<'a, SmallArrayList<T, N>>,
+    len: &'a mut usize,
+}
+
+impl<'a, T, const N: usize> CursorMut<'a, T, N> {
+    /// Returns the index of the current element, or `None` at the ghost element
+    pub fn index(&self) -> Option<usize> {
+        self.chunks.index().map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.chunks.current() {
+            Some(chunk) => {
+                self.offset += 1;
+                self.index += 1;
+
+                if self.offset == chunk.len() {
+                    self.chunks.move_next();
+                    self.offset = 0;
+                }
+            }
+            None => {
+                self.chunks.move_next();
+                self.offset = 0;
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if self.chunks.index().is_some() && self.offset > 0 {
+            self.offset -= 1;
+            self.index -= 1;
+            return;
+        }
+
+        let was_ghost = self.chunks.index().is_none();
+        self.chunks.move_prev();
+        self.offset = self.chunks.current().map_or(0, |chunk| chunk.len() - 1);
+
+        self.index = match self.chunks.index() {
+            Some(_) if was_ghost => *self.len - 1,
+            Some(_) => self.index - 1,
+            None => *self.len,
+        };
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        let offset = self.offset;
+        self.chunks.current()?.get_mut(offset)
+    }
+
+    /// Inserts `value` after the current element. At the ghost element this inserts
+    /// at the front of the list.
+    pub fn insert_after(&mut self, value: T) {
+        if self.chunks.index().is_some() {
+            self.insert_at(self.offset + 1, value);
+            return;
+        }
+
+        self.chunks.move_next();
+        if self.chunks.index().is_some() {
+            // Insert into the front chunk and step back onto the ghost element
+            self.offset = 0;
+            self.insert_at(0, value);
+            self.chunks.move_prev();
+        } else {
+            self.chunks.insert_after(Self::chunk_of(value));
+            *self.len += 1;
+        }
+
+        self.index = *self.len;
+    }
+
+    /// Inserts `value` before the current element. At the ghost element this inserts
+    /// at the back of the list.
+    pub fn insert_before(&mut self, value: T) {
+        if self.chunks.index().is_some() {
+            self.insert_at(self.offset, value);
+            self.offset += 1;
+            self.index += 1;
+            return;
+        }
+
+        match self.chunks.peek_prev() {
+            Some(tail) if tail.len() < N => tail.push(value),
+            _ => {
+                self.chunks.insert_before(Self::chunk_of(value));
+            }
+        }
+
+        *self.len += 1;
+        self.index = *self.len;
+    }
+
+    /// Removes the current element and moves the cursor to the next element.
+    /// Does nothing at the ghost element.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let offset = self.offset;
+        let chunk = self.chunks.current()?;
+        let value = chunk.remove(offset);
+        *self.len -= 1;
+
+        if chunk.is_empty() {
+            self.chunks.remove_current();
+            self.offset = 0;
+        } else {
+            self.rebalance_current();
+
+            if self.chunks.current().map(|chunk| chunk.len()) == Some(self.offset) {
+                self.chunks.move_next();
+                self.offset = 0;
+            }
+        }
+
+        return Some(value);
+    }
+}
+
+// private helper functions
+impl<'a, T, const N: usize> CursorMut<'a, T, N> {
+    fn chunk_of(value: T) -> SmallArrayList<T, N> {
+        let mut chunk = SmallArrayList::new();
+        chunk.push(value);
+
+        return chunk;
+    }
+
+    /// Inserts `value` at `at` in the current chunk, splitting the chunk first if it is
+    /// full. `at` must be the current offset or the one after it.
+    fn insert_at(&mut self, mut at: usize, value: T) {
+        let chunk = self.chunks.current().unwrap();
+
+        if chunk.len() == N {
+            let mid = N / 2;
+            let upper = chunk.split_off(mid);
+            self.chunks.insert_after(upper);
+
+            // Follow the current element if it moved to the new chunk
+            if self.offset >= mid {
+                self.chunks.move_next();
+                self.offset -= mid;
+                at -= mid;
+            }
+        }
+
+        self.chunks.current().unwrap().insert(at, value);
+        *self.len += 1;
+    }
+
+    /// Merges the current chunk with the next one if it dropped below half full and
+    /// both fit in one chunk, or else moves one element over from the next chunk
+    fn rebalance_current(&mut self) {
+        let len = self.chunks.current().map_or(0, |chunk| chunk.len());
+        if len >= N / 2 {
+            return;
+        }
+
+        let next_len = match self.chunks.peek_next() {
+            Some(next) => next.len(),
+            None => return,
+        };
+
+        if len + next_len <= N {
+            self.chunks.move_next();
+            let mut next = self.chunks.remove_current().unwrap();
+            self.chunks.move_prev();
+            self.chunks.current().unwrap().append(&mut next);
+        } else {
+            let value = self.chunks.peek_next().unwrap().remove(0);
+            self.chunks.current().unwrap().push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chunk_lens<T, const N: usize>(list: &UnrolledLinkedList<T, N>) -> Vec<usize> {
+        list.chunks.iter().map(|chunk| chunk.len()).collect()
+    }
+
+    /// Every chunk is non-empty and every chunk but the last is at least half full
+    fn assert_chunks_are_balanced<T, const N: usize>(list: &UnrolledLinkedList<T, N>) {
+        let lens = chunk_lens(list);
+
+        assert_eq!(lens.iter().sum::<usize>(), list.len());
+        assert!(lens.iter().all(|len| *len > 0 && *len <= N), "{:?}", lens);
+        if let Some((_, init)) = lens.split_last() {
+            assert!(init.iter().all(|len| *len >= N / 2), "{:?}", lens);
+        }
+    }
+
+    #[test]
+    fn can_push_and_pop() {
+        let mut list: UnrolledLinkedList<i32, 4> = UnrolledLinkedList::new();
+        for n in 0..10 {
+            list.push_back(n);
+        }
+        list.push_front(-1);
+
+        assert_eq!(list.len(), 11);
+        assert_eq!(list.front(), Some(&-1));
+        assert_eq!(list.back(), Some(&9));
+        assert_chunks_are_balanced(&list);
+
+        assert_eq!(list.pop_front(), Some(-1));
+        assert_eq!(list.pop_back(), Some(9));
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            (0..9).collect::<Vec<_>>()
+        );
+
+        while list.pop_front().is_some() {}
+        assert!(list.is_empty());
+        assert_eq!(list.pop_back(), None);
+        assert!(chunk_lens(&list).is_empty());
+    }
+
+    #[test]
+    fn can_get_and_get_mut() {
+        let mut list: UnrolledLinkedList<i32, 4> = UnrolledLinkedList::from_iter(0..10);
+
+        for n in 0..10 {
+            assert_eq!(list.get(n), Some(&(n as i32)));
+        }
+        assert_eq!(list.get(10), None);
+
+        *list.get_mut(5).unwrap() = 50;
+        assert_eq!(list.get(5), Some(&50));
+        assert_eq!(list.get_mut(10), None);
+    }
+
+    #[test]
+    fn can_insert_and_split_chunks() {
+        let mut list: UnrolledLinkedList<i32, 4> = UnrolledLinkedList::from_iter(0..4);
+        assert_eq!(chunk_lens(&list), vec![4]);
+
+        list.insert(1, 10);
+        assert_eq!(chunk_lens(&list), vec![3, 2]);
+        list.insert(4, 20);
+        list.insert(0, 30);
+        list.insert(list.len(), 40);
+
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![30, 0, 10, 1, 2, 20, 3, 40]
+        );
+        assert_chunks_are_balanced(&list);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds.")]
+    fn can_panic_when_inserting_out_of_bounds() {
+        let mut list: UnrolledLinkedList<i32, 4> = UnrolledLinkedList::from_iter(0..3);
+        list.insert(4, 4);
+    }
+
+    #[test]
+    fn can_remove_and_merge_chunks() {
+        let mut list: UnrolledLinkedList<i32, 4> = UnrolledLinkedList::from_iter(0..12);
+        assert_eq!(chunk_lens(&list), vec![4, 4, 4]);
+
+        // Borrows from the next chunk while both don't fit in one
+        assert_eq!(list.remove(0), Some(0));
+        assert_eq!(list.remove(0), Some(1));
+        assert_eq!(list.remove(0), Some(2));
+        assert_chunks_are_balanced(&list);
+
+        // Merges once they do
+        assert_eq!(list.remove(1), Some(4));
+        assert_eq!(list.remove(1), Some(5));
+        assert_chunks_are_balanced(&list);
+        assert_eq!(list.remove(list.len()), None);
+
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![3, 6, 7, 8, 9, 10, 11]
+        );
+    }
+
+    #[test]
+    fn can_move_cursor_in_both_directions() {
+        let mut list: UnrolledLinkedList<i32, 2> = UnrolledLinkedList::from_iter(0..5);
+        let mut cursor = list.cursor_front_mut();
+
+        for n in 0..5 {
+            assert_eq!(cursor.index(), Some(n as usize));
+            assert_eq!(cursor.current(), Some(&mut n.clone()));
+            cursor.move_next();
+        }
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+
+        for n in (0..5).rev() {
+            cursor.move_prev();
+            assert_eq!(cursor.index(), Some(n as usize));
+            assert_eq!(cursor.current(), Some(&mut n.clone()));
+        }
+
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(cursor.current(), Some(&mut 4));
+    }
+
+    #[test]
+    fn can_insert_with_cursor() {
+        let mut list: UnrolledLinkedList<i32, 4> = UnrolledLinkedList::from_iter(vec![1, 2, 3, 4]);
+        let mut cursor = list.cursor_front_mut();
+
+        cursor.move_next();
+        cursor.insert_before(10);
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 2));
+
+        cursor.insert_after(20);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 20));
+
+        // At the ghost element inserts go to either end
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        cursor.insert_before(30);
+        cursor.insert_after(40);
+        assert_eq!(cursor.index(), None);
+
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![40, 1, 10, 2, 20, 3, 4, 30]
+        );
+        assert_chunks_are_balanced(&list);
+    }
+
+    #[test]
+    fn can_remove_with_cursor() {
+        let mut list: UnrolledLinkedList<i32, 4> = UnrolledLinkedList::from_iter(0..10);
+        let mut cursor = list.cursor_front_mut();
+
+        // Remove every other element while walking forward
+        for n in 0..5 {
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(n * 2 + 1));
+            assert_eq!(cursor.index(), Some(n as usize + 1).filter(|i| *i < 5));
+        }
+        assert_eq!(cursor.remove_current(), None);
+
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 2, 4, 6, 8]
+        );
+        assert_chunks_are_balanced(&list);
+    }
+
+    #[test]
+    fn can_iterate_mutably() {
+        let mut list: UnrolledLinkedList<i32, 3> = UnrolledLinkedList::from_iter(0..7);
+
+        for n in list.iter_mut() {
+            *n *= 2;
+        }
+        for n in &mut list {
+            *n += 1;
+        }
+
+        let iter = list.iter();
+        assert_eq!(iter.len(), 7);
+        assert_eq!(
+            iter.copied().collect::<Vec<_>>(),
+            vec![1, 3, 5, 7, 9, 11, 13]
+        );
+        assert_eq!(format!("{:?}", list), "[1, 3, 5, 7, 9, 11, 13]");
+    }
+
+    #[test]
+    fn can_match_vec_under_random_edits() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(39);
+        let mut list: UnrolledLinkedList<u32, 4> = UnrolledLinkedList::new();
+        let mut vec = Vec::new();
+
+        for n in 0..2000 {
+            let index = rng.gen_range(0..=vec.len());
+            if rng.gen_bool(0.6) {
+                list.insert(index, n);
+                vec.insert(index, n);
+            } else {
+                let removed = (index < vec.len()).then(|| vec.remove(index));
+                assert_eq!(list.remove(index), removed);
+            }
+        }
+
+        assert_chunks_are_balanced(&list);
+        assert!(list.iter().eq(vec.iter()));
+    }
+}
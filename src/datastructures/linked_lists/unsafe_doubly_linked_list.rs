@@ -1,41 +1,73 @@
+use std::alloc::{self, Layout};
+use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::mem;
 use std::ptr::NonNull;
+use std::sync::atomic::{self, AtomicU64};
 
-pub struct DoublyLinkedList<T> {
+use crate::allocators::{Allocator, Global};
+use crate::datastructures::linked_lists::merge_sort::{merge_sort, SortLinks};
+
+/// Source of the ids that tie a `NodeHandle` to the list that issued it.
+/// `0` is reserved for lists that have not issued any handles yet.
+pub(crate) static NEXT_LIST_ID: AtomicU64 = AtomicU64::new(1);
+
+pub struct DoublyLinkedList<T, A: Allocator = Global> {
     head: Option<NonNull<Node<T>>>,
     tail: Option<NonNull<Node<T>>>,
     len: usize,
+    alloc: A,
+    id: u64,
+    slots: Vec<Slot<T>>,
+    free_slots: Vec<usize>,
 }
 
 #[derive(Debug, PartialEq)]
-pub(crate) struct Node<T> {
-    pub(crate) value: T,
+struct Node<T> {
+    value: T,
     next: Option<NonNull<Node<T>>>,
     prev: Option<NonNull<Node<T>>>,
+    /// Index of the slot in `DoublyLinkedList::slots` that may point to this node
+    slot: usize,
 }
 
 impl<T> Node<T> {
-    pub(crate) fn new(value: T) -> Self {
+    fn new(value: T) -> Self {
         Self {
             value,
             next: None,
             prev: None,
+            slot: usize::MAX,
         }
     }
+}
 
-    pub(crate) fn new_as_ptr(value: T) -> NonNull<Self> {
-        return NonNull::from(Box::leak(Box::new(Self::new(value))));
-    }
+/// Maps a `NodeHandle` to a node of the list. The generation is bumped every time the
+/// slot is freed so that stale handles can be detected without touching freed nodes.
+struct Slot<T> {
+    node: Option<NonNull<Node<T>>>,
+    generation: u64,
+}
+
+/// A stable reference to an element of a `DoublyLinkedList` that stays valid while the
+/// element is moved around or other elements are added and removed.
+///
+/// Handles are checked before use, so a handle of an element that was removed or that
+/// belongs to another list is simply rejected. Operations that move elements between
+/// lists (`append`, `prepend`, `splice`, `split_off` and the cursor splits and splices)
+/// only invalidate the handles of the elements that moved. New handles for those can be
+/// taken with `CursorMut::handle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    list_id: u64,
+    slot: usize,
+    generation: u64,
 }
 
 impl<T> DoublyLinkedList<T> {
     pub fn new() -> Self {
-        Self {
-            head: None,
-            tail: None,
-            len: 0,
-        }
+        Self::new_in(Global)
     }
 
     pub fn from_iter<I>(iter: I) -> Self
@@ -51,27 +83,52 @@ impl<T> DoublyLinkedList<T> {
 
         return list;
     }
+}
+
+impl<T, A: Allocator> DoublyLinkedList<T, A> {
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+            alloc,
+            id: 0,
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+        }
+    }
+
+    pub fn allocator(&self) -> &A {
+        return &self.alloc;
+    }
 
     pub fn insert(&mut self, index: usize, value: T) {
-        self.link_node_at(index, Node::new_as_ptr(value));
+        let node = self.allocate_node(value);
+        self.link_node_at(index, node);
     }
 
-    pub fn push_front(&mut self, value: T) {
-        self.push_front_node(Node::new_as_ptr(value));
+    /// Pushes `value` to the front of the list and returns a handle to it
+    pub fn push_front(&mut self, value: T) -> NodeHandle {
+        let node = self.allocate_node(value);
+        self.push_front_node(node);
+        return self.handle_of(node);
     }
 
-    pub fn push_back(&mut self, value: T) {
-        self.push_back_node(Node::new_as_ptr(value));
+    /// Pushes `value` to the back of the list and returns a handle to it
+    pub fn push_back(&mut self, value: T) -> NodeHandle {
+        let node = self.allocate_node(value);
+        self.push_back_node(node);
+        return self.handle_of(node);
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
         self.pop_front_node()
-            .map(|node| unsafe { Box::from_raw(node.as_ptr()).value })
+            .map(|node| unsafe { self.deallocate_node(node) })
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
         self.pop_back_node()
-            .map(|node| unsafe { Box::from_raw(node.as_ptr()).value })
+            .map(|node| unsafe { self.deallocate_node(node) })
     }
 
     pub fn get(&self, index: usize) -> Option<&T> {
@@ -81,7 +138,7 @@ impl<T> DoublyLinkedList<T> {
         }
     }
 
-    pub fn get_mut(&self, index: usize) -> Option<&mut T> {
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
         match self.get_node(index) {
             Some(node_ptr) => unsafe { Some(&mut (*node_ptr.as_ptr()).value) },
             None => None,
@@ -90,7 +147,7 @@ impl<T> DoublyLinkedList<T> {
 
     pub fn remove(&mut self, index: usize) -> Option<T> {
         match self.unlink_node_at(index) {
-            Some(node) => Some(unsafe { Box::from_raw(node.as_ptr()).value }),
+            Some(node) => Some(unsafe { self.deallocate_node(node) }),
             None => None,
         }
     }
@@ -130,8 +187,8 @@ impl<T> DoublyLinkedList<T> {
     pub fn to_vec(mut self) -> Vec<T> {
         let mut vec = Vec::with_capacity(self.len);
 
-        while let Some(node) = self.pop_front_node() {
-            vec.push(unsafe { Box::from_raw(node.as_ptr()).value });
+        while let Some(value) = self.pop_front() {
+            vec.push(value);
         }
 
         return vec;
@@ -154,10 +211,333 @@ impl<T> DoublyLinkedList<T> {
             phantom_data: PhantomData,
         }
     }
+
+    /// Returns `true` if `handle` refers to an element of this list
+    pub fn contains_handle(&self, handle: NodeHandle) -> bool {
+        return self.node_of(handle).is_some();
+    }
+
+    pub fn get_by_handle(&self, handle: NodeHandle) -> Option<&T> {
+        self.node_of(handle)
+            .map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn get_mut_by_handle(&mut self, handle: NodeHandle) -> Option<&mut T> {
+        self.node_of(handle)
+            .map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Removes the element referred to by `handle` in O(1)
+    pub fn remove_by_handle(&mut self, handle: NodeHandle) -> Option<T> {
+        let node = self.node_of(handle)?;
+
+        unsafe {
+            let node = self.unlink_node_unchecked(node);
+            return Some(self.deallocate_node(node));
+        }
+    }
+
+    /// Moves the element referred to by `handle` to the front of the list in O(1).
+    /// Returns `false` if the handle is not valid for this list.
+    pub fn move_to_front(&mut self, handle: NodeHandle) -> bool {
+        let node = match self.node_of(handle) {
+            Some(node) => node,
+            None => return false,
+        };
+
+        if self.head != Some(node) {
+            unsafe { self.unlink_node_unchecked(node) };
+            self.push_front_node(node);
+        }
+
+        return true;
+    }
+
+    /// Moves the element referred to by `handle` to the back of the list in O(1).
+    /// Returns `false` if the handle is not valid for this list.
+    pub fn move_to_back(&mut self, handle: NodeHandle) -> bool {
+        let node = match self.node_of(handle) {
+            Some(node) => node,
+            None => return false,
+        };
+
+        if self.tail != Some(node) {
+            unsafe { self.unlink_node_unchecked(node) };
+            self.push_back_node(node);
+        }
+
+        return true;
+    }
+
+    /// Moves all of the elements of `other` to the back of this list in O(1),
+    /// leaving `other` empty
+    pub fn append(&mut self, other: &mut Self) {
+        let tail = self.tail;
+        unsafe { self.splice_nodes(tail, None, other) };
+    }
+
+    /// Moves all of the elements of `other` to the front of this list in O(1),
+    /// leaving `other` empty
+    pub fn prepend(&mut self, other: &mut Self) {
+        let head = self.head;
+        unsafe { self.splice_nodes(None, head, other) };
+    }
+
+    /// Moves all of the elements of `other` into this list so that the first of them
+    /// ends up at `index`. Only finding `index` takes linear time, the relinking is O(1).
+    pub fn splice(&mut self, index: usize, mut other: Self) {
+        if index > self.len {
+            panic!("Index out of bounds.");
+        }
+
+        let (prev, next) = if index == 0 {
+            (None, self.head)
+        } else {
+            let prev = self.get_node(index - 1);
+            (prev, prev.and_then(|node| unsafe { (*node.as_ptr()).next }))
+        };
+
+        unsafe { self.splice_nodes(prev, next, &mut other) };
+    }
+
+    /// Returns a cursor pointing at the front element, or at the "ghost" element
+    /// if the list is empty
+    pub fn cursor_front(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            index: 0,
+            current: self.head,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor pointing at the back element, or at the "ghost" element
+    /// if the list is empty
+    pub fn cursor_back(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            index: self.len.saturating_sub(1),
+            current: self.tail,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor that can edit the list, pointing at the front element, or at
+    /// the "ghost" element if the list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            index: 0,
+            current: self.head,
+            list: self,
+        }
+    }
+
+    /// Returns a cursor that can edit the list, pointing at the back element, or at
+    /// the "ghost" element if the list is empty
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut {
+            index: self.len.saturating_sub(1),
+            current: self.tail,
+            list: self,
+        }
+    }
+}
+
+impl<T, A: Allocator> DoublyLinkedList<T, A> {
+    /// Sorts the list with a stable merge sort in O(n log n). Nodes are relinked rather
+    /// than moved, so values are never copied and handles stay valid.
+    ///
+    /// If `compare` panics the list is left in its original order.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len <= 1 {
+            return;
+        }
+
+        // The merges only rewrite `next`, so the untouched `prev` links can restore the
+        // original order if `compare` panics halfway through
+        struct PanicGuard<'a, T, A: Allocator>(&'a mut DoublyLinkedList<T, A>);
+
+        impl<T, A: Allocator> Drop for PanicGuard<'_, T, A> {
+            fn drop(&mut self) {
+                let mut next = None;
+                let mut curr_node = self.0.tail;
+                while let Some(node) = curr_node {
+                    unsafe {
+                        (*node.as_ptr()).next = next;
+                        curr_node = (*node.as_ptr()).prev;
+                    }
+                    next = Some(node);
+                }
+            }
+        }
+
+        let (head, len) = (self.head.unwrap(), self.len);
+        let guard = PanicGuard(self);
+        let (head, tail) =
+            unsafe { merge_sort(&mut SortOps(PhantomData), head, len, &mut compare) };
+        mem::forget(guard);
+
+        // The merges only maintain `next`, so restore `prev` afterwards
+        let mut prev = None;
+        let mut curr_node = Some(head);
+        while let Some(node) = curr_node {
+            unsafe {
+                (*node.as_ptr()).prev = prev;
+                curr_node = (*node.as_ptr()).next;
+            }
+            prev = Some(node);
+        }
+
+        self.head = Some(head);
+        self.tail = Some(tail);
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+}
+
+impl<T: Ord, A: Allocator> DoublyLinkedList<T, A> {
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp);
+    }
+}
+
+impl<T, A: Allocator + Clone> DoublyLinkedList<T, A> {
+    /// Splits the list in two at `index` and returns everything from `index` onwards.
+    /// Besides finding `index`, invalidating the handles of the split off elements takes
+    /// time linear in their number.
+    pub fn split_off(&mut self, index: usize) -> Self {
+        if index > self.len {
+            panic!("Index out of bounds.");
+        }
+
+        let node = match index {
+            0 => None,
+            _ => self.get_node(index - 1),
+        };
+
+        return unsafe { self.split_off_after_node(node, index) };
+    }
 }
 
 // Helper methods
-impl<T> DoublyLinkedList<T> {
+impl<T, A: Allocator> DoublyLinkedList<T, A> {
+    /// Allocates a new node and registers it in a slot so that handles can refer to it
+    fn allocate_node(&mut self, value: T) -> NonNull<Node<T>> {
+        let layout = Layout::new::<Node<T>>();
+        let node = match self.alloc.allocate(layout) {
+            Ok(ptr) => ptr.cast::<Node<T>>(),
+            Err(_) => alloc::handle_alloc_error(layout),
+        };
+
+        unsafe { node.as_ptr().write(Node::new(value)) };
+        self.assign_slot(node);
+
+        return node;
+    }
+
+    /// Registers `node` in a free slot of this list
+    fn assign_slot(&mut self, node: NonNull<Node<T>>) -> usize {
+        if self.id == 0 {
+            self.id = NEXT_LIST_ID.fetch_add(1, atomic::Ordering::Relaxed);
+        }
+
+        let slot = match self.free_slots.pop() {
+            Some(slot) => {
+                self.slots[slot].node = Some(node);
+                slot
+            }
+            None => {
+                self.slots.push(Slot {
+                    node: Some(node),
+                    generation: 0,
+                });
+                self.slots.len() - 1
+            }
+        };
+        unsafe { (*node.as_ptr()).slot = slot };
+
+        return slot;
+    }
+
+    /// Returns `true` if `node` is registered in a slot of this list
+    fn owns_slot(&self, node: NonNull<Node<T>>) -> bool {
+        let slot = unsafe { (*node.as_ptr()).slot };
+        match self.slots.get(slot) {
+            Some(slot) => slot.node == Some(node),
+            None => false,
+        }
+    }
+
+    /// Frees the slot of `node`, so that the handles to it are rejected
+    fn release_slot(&mut self, node: NonNull<Node<T>>) {
+        if self.owns_slot(node) {
+            let slot = unsafe { (*node.as_ptr()).slot };
+            self.slots[slot].node = None;
+            self.slots[slot].generation += 1;
+            self.free_slots.push(slot);
+        }
+    }
+
+    /// Moves the value out of an unlinked `node`, frees its slot and frees the node.
+    ///
+    /// WARN: `node` must have been allocated by a live allocator of type `A`, such as
+    /// the allocator of a list it was spliced from, and must not be linked into any list.
+    unsafe fn deallocate_node(&mut self, node: NonNull<Node<T>>) -> T {
+        // Nodes that were moved in from another list may not own a slot of this list
+        self.release_slot(node);
+        let node_value = node.as_ptr().read();
+
+        self.alloc.deallocate(node.cast(), Layout::new::<Node<T>>());
+        return node_value.value;
+    }
+
+    /// Returns a handle to `node`, first registering it in a slot if it was moved in from
+    /// another list.
+    ///
+    /// WARN: `node` must be part of this list
+    fn handle_of(&mut self, node: NonNull<Node<T>>) -> NodeHandle {
+        let slot = if self.owns_slot(node) {
+            unsafe { (*node.as_ptr()).slot }
+        } else {
+            self.assign_slot(node)
+        };
+
+        NodeHandle {
+            list_id: self.id,
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// Returns the node that `handle` refers to if it is still part of this list
+    fn node_of(&self, handle: NodeHandle) -> Option<NonNull<Node<T>>> {
+        if self.id == 0 || handle.list_id != self.id {
+            return None;
+        }
+
+        let slot = self.slots.get(handle.slot)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+
+        return slot.node;
+    }
+
+    /// Drops every slot and takes a new id, so that all of the handles issued so far
+    /// are rejected. Used when every node of the list has moved to another list.
+    fn invalidate_handles(&mut self) {
+        self.id = 0;
+        self.slots.clear();
+        self.free_slots.clear();
+    }
+
     fn get_node(&self, index: usize) -> Option<NonNull<Node<T>>> {
         if index >= self.len {
             return None;
@@ -207,10 +587,10 @@ impl<T> DoublyLinkedList<T> {
         return node;
     }
 
-    pub(crate) fn push_front_node(&mut self, new_front: NonNull<Node<T>>) {
+    fn push_front_node(&mut self, new_front: NonNull<Node<T>>) {
         match self.head {
             Some(old_front) => {
-                unsafe { Self::link_node_before(old_front, new_front) };
+                unsafe { link_node_before::<Node<T>>(old_front, new_front) };
                 self.head = Some(new_front);
             }
             None => {
@@ -221,7 +601,7 @@ impl<T> DoublyLinkedList<T> {
         self.len += 1;
     }
 
-    pub(crate) fn pop_front_node(&mut self) -> Option<NonNull<Node<T>>> {
+    fn pop_front_node(&mut self) -> Option<NonNull<Node<T>>> {
         self.head.map(|prev_head| unsafe {
             if let None = (*prev_head.as_ptr()).next {
                 self.tail = None;
@@ -229,14 +609,14 @@ impl<T> DoublyLinkedList<T> {
             self.head = (*prev_head.as_ptr()).next;
             self.len -= 1;
 
-            return Self::unlink_node(prev_head);
+            return unlink_node::<Node<T>>(prev_head);
         })
     }
 
-    pub(crate) fn push_back_node(&mut self, new_back: NonNull<Node<T>>) {
+    fn push_back_node(&mut self, new_back: NonNull<Node<T>>) {
         match self.tail {
             Some(old_back) => {
-                unsafe { Self::link_node_after(old_back, new_back) };
+                unsafe { link_node_after::<Node<T>>(old_back, new_back) };
                 self.tail = Some(new_back);
             }
             None => {
@@ -247,7 +627,7 @@ impl<T> DoublyLinkedList<T> {
         self.len += 1;
     }
 
-    pub(crate) fn pop_back_node(&mut self) -> Option<NonNull<Node<T>>> {
+    fn pop_back_node(&mut self) -> Option<NonNull<Node<T>>> {
         self.tail.map(|prev_tail| unsafe {
             if let None = (*prev_tail.as_ptr()).prev {
                 self.head = None;
@@ -255,11 +635,11 @@ impl<T> DoublyLinkedList<T> {
             self.tail = (*prev_tail.as_ptr()).prev;
             self.len -= 1;
 
-            return Self::unlink_node(prev_tail);
+            return unlink_node::<Node<T>>(prev_tail);
         })
     }
 
-    pub(crate) fn link_node_at(&mut self, index: usize, new_node: NonNull<Node<T>>) {
+    fn link_node_at(&mut self, index: usize, new_node: NonNull<Node<T>>) {
         if index > self.len {
             panic!("Index out of bounds.");
         }
@@ -270,7 +650,7 @@ impl<T> DoublyLinkedList<T> {
             self.push_back_node(new_node);
         } else {
             if let Some(node_at_index) = self.get_node(index) {
-                unsafe { Self::link_node_before(node_at_index, new_node) };
+                unsafe { link_node_before::<Node<T>>(node_at_index, new_node) };
                 self.len += 1;
             } else {
                 panic!("There should be a node at the specified index.");
@@ -278,7 +658,7 @@ impl<T> DoublyLinkedList<T> {
         }
     }
 
-    pub(crate) fn unlink_node_at(&mut self, index: usize) -> Option<NonNull<Node<T>>> {
+    fn unlink_node_at(&mut self, index: usize) -> Option<NonNull<Node<T>>> {
         if index == 0 {
             return self.pop_front_node();
         } else if index == self.len - 1 {
@@ -287,7 +667,7 @@ impl<T> DoublyLinkedList<T> {
             match self.get_node(index) {
                 Some(node) => {
                     self.len -= 1;
-                    return Some(unsafe { Self::unlink_node(node) });
+                    return Some(unsafe { unlink_node::<Node<T>>(node) });
                 }
                 None => None,
             }
@@ -295,10 +675,7 @@ impl<T> DoublyLinkedList<T> {
     }
 
     /// WARN: This method does not thoroughly check if `node` is part of the linked list.
-    pub(crate) unsafe fn unlink_node_unchecked(
-        &mut self,
-        node: NonNull<Node<T>>,
-    ) -> NonNull<Node<T>> {
+    unsafe fn unlink_node_unchecked(&mut self, node: NonNull<Node<T>>) -> NonNull<Node<T>> {
         // Ensure that `self.head` and `self.tail` pointers will get
         // handled appropriately when unlinking head and tail nodes
         if let Some(head_node) = self.head {
@@ -313,75 +690,232 @@ impl<T> DoublyLinkedList<T> {
         }
 
         self.len -= 1;
-        return Self::unlink_node(node);
+        return unlink_node::<Node<T>>(node);
     }
-}
 
-// Helper functions
-impl<T> DoublyLinkedList<T> {
-    /// WARN: This method does not update the length or any other internal state
-    /// of the linked list.
-    pub(crate) unsafe fn unlink_node(node: NonNull<Node<T>>) -> NonNull<Node<T>> {
-        let prev_node = (*node.as_ptr()).prev;
-        let next_node = (*node.as_ptr()).next;
+    /// Moves all of the nodes of `other` between the adjacent nodes `prev` and `next`,
+    /// where `None` stands for the respective end of the list. `other` is left empty.
+    ///
+    /// WARN: `prev` and `next` must be adjacent nodes of this list.
+    unsafe fn splice_nodes(
+        &mut self,
+        prev: Option<NonNull<Node<T>>>,
+        next: Option<NonNull<Node<T>>>,
+        other: &mut Self,
+    ) {
+        let (other_head, other_tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => return,
+        };
 
-        if let Some(prev) = prev_node {
-            (*prev.as_ptr()).next = next_node;
-            (*node.as_ptr()).prev = None;
+        // The nodes of this list stay put, so only the handles of `other` go stale. The
+        // moved nodes get a slot of this list once a handle to them is asked for.
+        other.invalidate_handles();
+
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = Some(other_head),
+            None => self.head = Some(other_head),
         }
+        (*other_head.as_ptr()).prev = prev;
 
-        if let Some(next) = next_node {
-            (*next.as_ptr()).prev = prev_node;
-            (*node.as_ptr()).next = None;
+        match next {
+            Some(next) => (*next.as_ptr()).prev = Some(other_tail),
+            None => self.tail = Some(other_tail),
         }
+        (*other_tail.as_ptr()).next = next;
 
-        return node;
+        self.len += mem::replace(&mut other.len, 0);
     }
 
-    /// Links `new_node` after node `after` and returns a `NonNull` pointer to `new_node`
-    /// WARN: This method does not update the length or any other internal state
-    /// of the linked list.
-    unsafe fn link_node_after(
-        after: NonNull<Node<T>>,
-        new_node: NonNull<Node<T>>,
-    ) -> NonNull<Node<T>> {
-        let next_node = (*after.as_ptr()).next;
+    /// Frees the slots of the detached chain starting at `head`, which takes linear time
+    /// unless the whole list was detached and every slot can be dropped at once.
+    ///
+    /// WARN: The chain must have been unlinked from this list already
+    unsafe fn release_slots(&mut self, head: Option<NonNull<Node<T>>>, whole_list: bool) {
+        if whole_list {
+            self.invalidate_handles();
+            return;
+        }
 
-        if let Some(next) = next_node {
-            (*next.as_ptr()).prev = Some(new_node);
+        let mut curr_node = head;
+        while let Some(node) = curr_node {
+            self.release_slot(node);
+            curr_node = (*node.as_ptr()).next;
         }
+    }
+
+    /// Detaches every node after `node` into a new list, where `None` detaches the whole
+    /// list. `at` is the number of nodes that stay in this list.
+    ///
+    /// WARN: `node` must be the node at index `at - 1` of this list.
+    unsafe fn split_off_after_node(&mut self, node: Option<NonNull<Node<T>>>, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        let mut split = Self::new_in(self.alloc.clone());
+
+        let split_head = match node {
+            Some(node) => (*node.as_ptr()).next.take(),
+            None => self.head.take(),
+        };
 
-        (*new_node.as_ptr()).next = next_node;
-        (*new_node.as_ptr()).prev = Some(after);
+        if let Some(split_head) = split_head {
+            (*split_head.as_ptr()).prev = None;
+            self.release_slots(Some(split_head), node.is_none());
 
-        (*after.as_ptr()).next = Some(new_node);
+            split.head = Some(split_head);
+            split.tail = mem::replace(&mut self.tail, node);
+            split.len = self.len - at;
+            self.len = at;
+        }
 
-        return new_node;
+        return split;
     }
 
-    /// Links `new_node` before node `before` and returns a `NonNull` pointer to `new_node`
-    /// WARN: This method does not update the length or any other internal state
-    /// of the linked list.
-    unsafe fn link_node_before(
-        before: NonNull<Node<T>>,
-        new_node: NonNull<Node<T>>,
-    ) -> NonNull<Node<T>> {
-        let prev_node = (*before.as_ptr()).prev;
+    /// Detaches every node before `node` into a new list, where `None` detaches the whole
+    /// list. `at` is the number of nodes that get detached.
+    ///
+    /// WARN: `node` must be the node at index `at` of this list.
+    unsafe fn split_off_before_node(&mut self, node: Option<NonNull<Node<T>>>, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        let mut split = Self::new_in(self.alloc.clone());
+
+        let split_tail = match node {
+            Some(node) => (*node.as_ptr()).prev.take(),
+            None => self.tail.take(),
+        };
+
+        if let Some(split_tail) = split_tail {
+            (*split_tail.as_ptr()).next = None;
+            let split_head = mem::replace(&mut self.head, node);
+            self.release_slots(split_head, node.is_none());
 
-        if let Some(prev) = prev_node {
-            (*prev.as_ptr()).next = Some(new_node);
+            split.head = split_head;
+            split.tail = Some(split_tail);
+            split.len = at;
+            self.len -= at;
         }
 
-        (*new_node.as_ptr()).prev = prev_node;
-        (*new_node.as_ptr()).next = Some(before);
+        return split;
+    }
+}
+
+/// Lets `merge_sort` follow the `next` pointers of the nodes
+struct SortOps<T>(PhantomData<T>);
+
+impl<T> SortLinks for SortOps<T> {
+    type Link = NonNull<Node<T>>;
+    type Value = T;
+
+    unsafe fn next(&self, node: NonNull<Node<T>>) -> Option<NonNull<Node<T>>> {
+        (*node.as_ptr()).next
+    }
+
+    unsafe fn set_next(&mut self, node: NonNull<Node<T>>, next: Option<NonNull<Node<T>>>) {
+        (*node.as_ptr()).next = next;
+    }
+
+    unsafe fn value(&self, node: NonNull<Node<T>>) -> &T {
+        &(*node.as_ptr()).value
+    }
+}
+
+/// Gives the relinking helpers below access to the `next` and `prev` pointers of a
+/// node, so that every doubly linked list in the crate can share them
+pub(crate) trait LinkOps {
+    type Node;
+
+    unsafe fn next(node: NonNull<Self::Node>) -> Option<NonNull<Self::Node>>;
+    unsafe fn prev(node: NonNull<Self::Node>) -> Option<NonNull<Self::Node>>;
+    unsafe fn set_next(node: NonNull<Self::Node>, next: Option<NonNull<Self::Node>>);
+    unsafe fn set_prev(node: NonNull<Self::Node>, prev: Option<NonNull<Self::Node>>);
+}
+
+impl<T> LinkOps for Node<T> {
+    type Node = Self;
+
+    unsafe fn next(node: NonNull<Self>) -> Option<NonNull<Self>> {
+        (*node.as_ptr()).next
+    }
+
+    unsafe fn prev(node: NonNull<Self>) -> Option<NonNull<Self>> {
+        (*node.as_ptr()).prev
+    }
+
+    unsafe fn set_next(node: NonNull<Self>, next: Option<NonNull<Self>>) {
+        (*node.as_ptr()).next = next;
+    }
+
+    unsafe fn set_prev(node: NonNull<Self>, prev: Option<NonNull<Self>>) {
+        (*node.as_ptr()).prev = prev;
+    }
+}
+
+// Helper functions
+/// WARN: This method does not update the length or any other internal state
+/// of the linked list.
+pub(crate) unsafe fn unlink_node<L: LinkOps>(node: NonNull<L::Node>) -> NonNull<L::Node> {
+    let prev_node = L::prev(node);
+    let next_node = L::next(node);
+
+    if let Some(prev) = prev_node {
+        L::set_next(prev, next_node);
+        L::set_prev(node, None);
+    }
+
+    if let Some(next) = next_node {
+        L::set_prev(next, prev_node);
+        L::set_next(node, None);
+    }
+
+    return node;
+}
+
+/// Links `new_node` after node `after` and returns a `NonNull` pointer to `new_node`
+/// WARN: This method does not update the length or any other internal state
+/// of the linked list.
+pub(crate) unsafe fn link_node_after<L: LinkOps>(
+    after: NonNull<L::Node>,
+    new_node: NonNull<L::Node>,
+) -> NonNull<L::Node> {
+    let next_node = L::next(after);
+
+    if let Some(next) = next_node {
+        L::set_prev(next, Some(new_node));
+    }
+
+    L::set_next(new_node, next_node);
+    L::set_prev(new_node, Some(after));
+
+    L::set_next(after, Some(new_node));
+
+    return new_node;
+}
 
-        (*before.as_ptr()).prev = Some(new_node);
+/// Links `new_node` before node `before` and returns a `NonNull` pointer to `new_node`
+/// WARN: This method does not update the length or any other internal state
+/// of the linked list.
+pub(crate) unsafe fn link_node_before<L: LinkOps>(
+    before: NonNull<L::Node>,
+    new_node: NonNull<L::Node>,
+) -> NonNull<L::Node> {
+    let prev_node = L::prev(before);
 
-        return new_node;
+    if let Some(prev) = prev_node {
+        L::set_next(prev, Some(new_node));
     }
+
+    L::set_prev(new_node, prev_node);
+    L::set_next(new_node, Some(before));
+
+    L::set_prev(before, Some(new_node));
+
+    return new_node;
 }
 
-impl<T: Debug> Debug for DoublyLinkedList<T> {
+impl<T: Debug, A: Allocator> Debug for DoublyLinkedList<T, A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("[")?;
         unsafe {
@@ -400,50 +934,47 @@ impl<T: Debug> Debug for DoublyLinkedList<T> {
     }
 }
 
-impl<T> Drop for DoublyLinkedList<T> {
+// The list owns its nodes like a `Box<Node<T>>` would, so it is as thread safe as the
+// values and the allocator it holds
+unsafe impl<T: Send, A: Allocator + Send> Send for DoublyLinkedList<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for DoublyLinkedList<T, A> {}
+
+impl<T, A: Allocator> Drop for DoublyLinkedList<T, A> {
     fn drop(&mut self) {
         self.clear();
     }
 }
 
-impl<T> IntoIterator for DoublyLinkedList<T> {
+impl<T, A: Allocator> IntoIterator for DoublyLinkedList<T, A> {
     type Item = T;
 
-    type IntoIter = IntoIter<Self::Item>;
+    type IntoIter = IntoIter<Self::Item, A>;
 
-    fn into_iter(mut self) -> Self::IntoIter {
-        return Self::IntoIter {
-            next_node: self.head.take(),
-            next_back_node: self.tail.take(),
-            len: self.len,
-        };
+    fn into_iter(self) -> Self::IntoIter {
+        return Self::IntoIter { list: self };
     }
 }
 
-#[derive(Debug)]
-pub struct IntoIter<T> {
-    next_node: Option<NonNull<Node<T>>>,
-    next_back_node: Option<NonNull<Node<T>>>,
-    len: usize,
+/// Owns the remaining nodes so that they are freed if the iterator is dropped early
+pub struct IntoIter<T, A: Allocator = Global> {
+    list: DoublyLinkedList<T, A>,
+}
+
+impl<T: Debug, A: Allocator> Debug for IntoIter<T, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.list).finish()
+    }
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.len == 0 {
-            return None;
-        }
-
-        return self.next_node.map(|node| unsafe {
-            self.next_node = (*node.as_ptr()).next;
-            self.len -= 1;
-            return Box::from_raw(node.as_ptr()).value;
-        });
+        return self.list.pop_front();
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+        (self.list.len, Some(self.list.len))
     }
 
     fn last(mut self) -> Option<T> {
@@ -451,17 +982,9 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.len == 0 {
-            return None;
-        }
-
-        return self.next_back_node.map(|node| unsafe {
-            self.next_back_node = (*node.as_ptr()).prev;
-            self.len -= 1;
-            return Box::from_raw(node.as_ptr()).value;
-        });
+        return self.list.pop_back();
     }
 }
 
@@ -469,9 +992,13 @@ pub struct Iter<'a, T> {
     next_node: Option<NonNull<Node<T>>>,
     next_back_node: Option<NonNull<Node<T>>>,
     len: usize,
-    phantom_data: PhantomData<&'a DoublyLinkedList<T>>,
+    phantom_data: PhantomData<&'a T>,
 }
 
+// Behaves like a `&'a T`
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
@@ -514,9 +1041,13 @@ pub struct IterMut<'a, T> {
     next_node: Option<NonNull<Node<T>>>,
     next_back_node: Option<NonNull<Node<T>>>,
     len: usize,
-    phantom_data: PhantomData<&'a mut DoublyLinkedList<T>>,
+    phantom_data: PhantomData<&'a mut T>,
 }
 
+// Behaves like a `&'a mut T`
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
 impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
@@ -555,24 +1086,324 @@ impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn can_construct_from_iterator_and_to_vec() {
-        let vec = vec![1, 2, 3, 4, 5, 6, 7];
-        let linked_list = DoublyLinkedList::from_iter(vec.clone());
+/// A cursor over a `DoublyLinkedList` that can move in both directions.
+///
+/// Besides the elements of the list the cursor can also point at a "ghost" element
+/// that sits between the back and the front of the list, which is where it ends up
+/// after moving past either end.
+pub struct Cursor<'a, T, A: Allocator = Global> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a DoublyLinkedList<T, A>,
+}
 
-        can_walk_forward_and_back(&linked_list);
+// Behaves like a `&'a DoublyLinkedList<T, A>`
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Send for Cursor<'a, T, A> {}
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Sync for Cursor<'a, T, A> {}
 
-        assert_eq!(linked_list.to_vec(), vec);
+impl<'a, T, A: Allocator> Clone for Cursor<'a, T, A> {
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            current: self.current,
+            list: self.list,
+        }
     }
+}
 
-    #[test]
-    fn can_push_front() {
-        let vec = vec![1, 2, 3, 4, 5, 6, 7];
-        let mut linked_list = DoublyLinkedList::new();
+impl<'a, T, A: Allocator> Cursor<'a, T, A> {
+    /// Returns the index of the current element, or `None` at the ghost element
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(node) => unsafe {
+                self.current = (*node.as_ptr()).next;
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(node) => unsafe {
+                self.current = (*node.as_ptr()).prev;
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.len);
+            },
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        self.current.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head,
+        };
+
+        next.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail,
+        };
+
+        prev.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+}
+
+/// A cursor over a `DoublyLinkedList` that can also edit the list around its position
+/// in O(1), except for splitting which has to invalidate the handles of the split off
+/// elements.
+///
+/// Besides the elements of the list the cursor can also point at a "ghost" element
+/// that sits between the back and the front of the list, which is where it ends up
+/// after moving past either end.
+pub struct CursorMut<'a, T, A: Allocator = Global> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a mut DoublyLinkedList<T, A>,
+}
+
+// Behaves like a `&'a mut DoublyLinkedList<T, A>`
+unsafe impl<'a, T: Send, A: Allocator + Send> Send for CursorMut<'a, T, A> {}
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Sync for CursorMut<'a, T, A> {}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    /// Returns the index of the current element, or `None` at the ghost element
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(node) => unsafe {
+                self.current = (*node.as_ptr()).next;
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(node) => unsafe {
+                self.current = (*node.as_ptr()).prev;
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.len);
+            },
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current
+            .map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head,
+        };
+
+        next.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail,
+        };
+
+        prev.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns a read-only cursor at the current position
+    pub fn as_cursor(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            index: self.index,
+            current: self.current,
+            list: self.list,
+        }
+    }
+
+    /// Returns a handle to the current element, or `None` at the ghost element. This
+    /// also works for elements that were moved in from another list.
+    pub fn handle(&mut self) -> Option<NodeHandle> {
+        let node = self.current?;
+        return Some(self.list.handle_of(node));
+    }
+
+    /// Inserts `value` after the current element and returns a handle to it. At the
+    /// ghost element this inserts at the front of the list.
+    pub fn insert_after(&mut self, value: T) -> NodeHandle {
+        let node = self.list.allocate_node(value);
+
+        match self.current {
+            Some(current) if Some(current) == self.list.tail => self.list.push_back_node(node),
+            Some(current) => {
+                unsafe { link_node_after::<Node<T>>(current, node) };
+                self.list.len += 1;
+            }
+            None => {
+                self.list.push_front_node(node);
+                self.index = self.list.len;
+            }
+        }
+
+        return self.list.handle_of(node);
+    }
+
+    /// Inserts `value` before the current element and returns a handle to it. At the
+    /// ghost element this inserts at the back of the list.
+    pub fn insert_before(&mut self, value: T) -> NodeHandle {
+        let node = self.list.allocate_node(value);
+
+        match self.current {
+            Some(current) if Some(current) == self.list.head => {
+                self.list.push_front_node(node);
+                self.index += 1;
+            }
+            Some(current) => {
+                unsafe { link_node_before::<Node<T>>(current, node) };
+                self.list.len += 1;
+                self.index += 1;
+            }
+            None => {
+                self.list.push_back_node(node);
+                self.index = self.list.len;
+            }
+        }
+
+        return self.list.handle_of(node);
+    }
+
+    /// Removes the current element and moves the cursor to the next element.
+    /// Does nothing at the ghost element.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+
+        unsafe {
+            self.current = (*node.as_ptr()).next;
+            let node = self.list.unlink_node_unchecked(node);
+            return Some(self.list.deallocate_node(node));
+        }
+    }
+
+    /// Moves all of the elements of `other` in between the current and the next element
+    /// without allocating. At the ghost element they are moved to the front of the list.
+    pub fn splice_after(&mut self, mut other: DoublyLinkedList<T, A>) {
+        unsafe {
+            match self.current {
+                Some(current) => {
+                    let next = (*current.as_ptr()).next;
+                    self.list.splice_nodes(Some(current), next, &mut other);
+                }
+                None => {
+                    let head = self.list.head;
+                    self.list.splice_nodes(None, head, &mut other);
+                    self.index = self.list.len;
+                }
+            }
+        }
+    }
+
+    /// Moves all of the elements of `other` in between the previous and the current
+    /// element without allocating. At the ghost element they are moved to the back of
+    /// the list.
+    pub fn splice_before(&mut self, mut other: DoublyLinkedList<T, A>) {
+        let other_len = other.len;
+
+        unsafe {
+            match self.current {
+                Some(current) => {
+                    let prev = (*current.as_ptr()).prev;
+                    self.list.splice_nodes(prev, Some(current), &mut other);
+                    self.index += other_len;
+                }
+                None => {
+                    let tail = self.list.tail;
+                    self.list.splice_nodes(tail, None, &mut other);
+                    self.index = self.list.len;
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T, A: Allocator + Clone> CursorMut<'a, T, A> {
+    /// Splits the list after the current element and returns everything after it.
+    /// At the ghost element the whole list is returned in O(1), otherwise invalidating
+    /// the handles of the split off elements takes time linear in their number.
+    pub fn split_after(&mut self) -> DoublyLinkedList<T, A> {
+        let at = match self.current {
+            Some(_) => self.index + 1,
+            None => 0,
+        };
+
+        let split = unsafe { self.list.split_off_after_node(self.current, at) };
+        if self.current.is_none() {
+            self.index = 0;
+        }
+
+        return split;
+    }
+
+    /// Splits the list before the current element and returns everything before it.
+    /// At the ghost element the whole list is returned in O(1), otherwise invalidating
+    /// the handles of the split off elements takes time linear in their number.
+    pub fn split_before(&mut self) -> DoublyLinkedList<T, A> {
+        let at = match self.current {
+            Some(_) => self.index,
+            None => self.list.len,
+        };
+
+        let split = unsafe { self.list.split_off_before_node(self.current, at) };
+        self.index = 0;
+
+        return split;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::allocators::BumpAllocator;
+
+    #[test]
+    fn can_construct_from_iterator_and_to_vec() {
+        let vec = vec![1, 2, 3, 4, 5, 6, 7];
+        let linked_list = DoublyLinkedList::from_iter(vec.clone());
+
+        can_walk_forward_and_back(&linked_list);
+
+        assert_eq!(linked_list.to_vec(), vec);
+    }
+
+    #[test]
+    fn can_push_front() {
+        let vec = vec![1, 2, 3, 4, 5, 6, 7];
+        let mut linked_list = DoublyLinkedList::new();
 
         for n in vec.iter().rev() {
             linked_list.push_front(*n);
@@ -596,7 +1427,7 @@ mod test {
     #[test]
     fn can_get_and_mutate_value_by_index() {
         let vec = vec![1, 2, 3, 4, 5, 6, 7];
-        let linked_list = DoublyLinkedList::from_iter(vec.clone());
+        let mut linked_list = DoublyLinkedList::from_iter(vec.clone());
 
         for idx in 0..linked_list.len() {
             *linked_list.get_mut(idx).unwrap() += 1;
@@ -913,12 +1744,776 @@ mod test {
         assert_eq!(iter_mut.next(), None);
     }
 
+    #[test]
+    fn can_use_custom_allocator() {
+        let arena = BumpAllocator::with_chunk_size(128);
+        let mut linked_list = DoublyLinkedList::new_in(&arena);
+
+        for n in 0..50 {
+            linked_list.push_back(n.to_string());
+        }
+        linked_list.push_front(String::from("first"));
+        linked_list.insert(1, String::from("second"));
+        assert_eq!(linked_list.remove(2), Some(String::from("0")));
+        assert_eq!(linked_list.pop_back(), Some(String::from("49")));
+
+        can_walk_forward_and_back(&linked_list);
+        assert_eq!(linked_list.len(), 50);
+        assert!(arena.allocated_bytes() > 0);
+
+        let mut into_iter = linked_list.into_iter();
+        assert_eq!(into_iter.next().as_deref(), Some("first"));
+        assert_eq!(into_iter.next_back().as_deref(), Some("48"));
+    }
+
+    #[test]
+    fn can_move_cursor_in_both_directions() {
+        let linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3]);
+        let mut cursor = linked_list.cursor_front();
+
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.peek_next(), Some(&2));
+
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+
+        // Ghost element between the back and the front
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some(&1));
+        assert_eq!(cursor.peek_prev(), Some(&3));
+
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&1));
+
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+
+        let cursor = linked_list.cursor_back();
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+    }
+
+    #[test]
+    fn can_move_cursor_on_empty_list() {
+        let mut linked_list: DoublyLinkedList<i32> = DoublyLinkedList::new();
+        let mut cursor = linked_list.cursor_front_mut();
+
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.remove_current(), None);
+    }
+
+    #[test]
+    fn can_insert_with_cursor() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![2, 4]);
+        let mut cursor = linked_list.cursor_front_mut();
+
+        cursor.insert_before(1); // [1, (2), 4]
+        assert_eq!(cursor.index(), Some(1));
+
+        cursor.insert_after(3); // [1, (2), 3, 4]
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(cursor.index(), Some(3));
+
+        cursor.insert_after(5); // [1, 2, 3, (4), 5]
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+
+        cursor.insert_after(0); // [0, 1, 2, 3, 4, 5]
+        cursor.insert_before(6); // [0, 1, 2, 3, 4, 5, 6]
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 6));
+        assert_eq!(cursor.index(), Some(6));
+
+        can_walk_forward_and_back(&linked_list);
+        assert_eq!(linked_list.to_vec(), vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn can_mutate_with_cursor() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3]);
+        let mut cursor = linked_list.cursor_front_mut();
+
+        while let Some(value) = cursor.current() {
+            *value *= 10;
+            cursor.move_next();
+        }
+        *cursor.peek_next().unwrap() += 1;
+        *cursor.peek_prev().unwrap() += 1;
+        assert_eq!(cursor.as_cursor().peek_prev(), Some(&31));
+
+        assert_eq!(linked_list.to_vec(), vec![11, 20, 31]);
+    }
+
+    #[test]
+    fn can_remove_with_cursor() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+        let mut cursor = linked_list.cursor_front_mut();
+
+        assert_eq!(cursor.remove_current(), Some(1)); // [(2), 3, 4, 5]
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(0));
+
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3)); // [2, (4), 5]
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(cursor.index(), Some(1));
+
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(5)); // [2, 4, ()]
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        can_walk_forward_and_back(&linked_list);
+        assert_eq!(linked_list.len(), 2);
+        assert_eq!(linked_list.to_vec(), vec![2, 4]);
+    }
+
+    #[test]
+    fn can_split_with_cursor() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+        let mut cursor = linked_list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        let after = cursor.split_after(); // [1, 2, (3)] [4, 5]
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(2));
+
+        let before = cursor.split_before(); // [1, 2] [(3)]
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.split_after().len(), 0);
+        assert_eq!(cursor.split_before().len(), 0);
+
+        can_walk_forward_and_back(&linked_list);
+        can_walk_forward_and_back(&before);
+        can_walk_forward_and_back(&after);
+        assert_eq!(linked_list.len(), 1);
+        assert_eq!(before.to_vec(), vec![1, 2]);
+        assert_eq!(after.to_vec(), vec![4, 5]);
+
+        // The whole list is split off at the ghost element
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3]);
+        let mut cursor = linked_list.cursor_back_mut();
+        cursor.move_next();
+        let all = cursor.split_before();
+        assert_eq!(linked_list.len(), 0);
+        assert_eq!(all.to_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn can_splice_with_cursor() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 5]);
+        let mut cursor = linked_list.cursor_front_mut();
+
+        cursor.splice_after(DoublyLinkedList::from_iter(vec![2, 3])); // [(1), 2, 3, 5]
+        assert_eq!(cursor.index(), Some(0));
+
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.splice_before(DoublyLinkedList::from_iter(vec![4])); // [1, 2, 3, 4, (5)]
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(4));
+
+        // At the ghost element
+        cursor.move_next();
+        cursor.splice_after(DoublyLinkedList::from_iter(vec![-1, 0]));
+        cursor.splice_before(DoublyLinkedList::from_iter(vec![6, 7]));
+        cursor.splice_before(DoublyLinkedList::new());
+
+        can_walk_forward_and_back(&linked_list);
+        assert_eq!(linked_list.len(), 9);
+        assert_eq!(linked_list.to_vec(), vec![-1, 0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn can_append() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3]);
+        let mut other = DoublyLinkedList::from_iter(vec![4, 5]);
+
+        linked_list.append(&mut other);
+        can_walk_forward_and_back(&linked_list);
+        assert_eq!(linked_list.len(), 5);
+        assert_eq!(other.len(), 0);
+        assert_eq!(other.front(), None);
+
+        // Appending to and from empty lists
+        linked_list.append(&mut other);
+        other.append(&mut linked_list);
+        can_walk_forward_and_back(&other);
+        assert_eq!(linked_list.len(), 0);
+        assert_eq!(other.to_vec(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn can_prepend() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![4, 5]);
+        let mut other = DoublyLinkedList::from_iter(vec![1, 2, 3]);
+
+        linked_list.prepend(&mut other);
+        can_walk_forward_and_back(&linked_list);
+        assert_eq!(other.len(), 0);
+
+        linked_list.prepend(&mut other);
+        assert_eq!(linked_list.to_vec(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn can_split_off() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+
+        let back = linked_list.split_off(3);
+        can_walk_forward_and_back(&linked_list);
+        can_walk_forward_and_back(&back);
+        assert_eq!(linked_list.len(), 3);
+        assert_eq!(back.len(), 2);
+        assert_eq!(back.to_vec(), vec![4, 5]);
+
+        let empty = linked_list.split_off(3);
+        assert_eq!(empty.len(), 0);
+        can_walk_forward_and_back(&linked_list);
+
+        let all = linked_list.split_off(0);
+        can_walk_forward_and_back(&all);
+        assert_eq!(linked_list.len(), 0);
+        assert_eq!(linked_list.front(), None);
+        assert_eq!(all.to_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds.")]
+    fn can_panic_when_splitting_off_out_of_bounds() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3]);
+        linked_list.split_off(4);
+    }
+
+    #[test]
+    fn can_splice() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 5]);
+
+        linked_list.splice(1, DoublyLinkedList::from_iter(vec![2, 3, 4])); // Middle
+        can_walk_forward_and_back(&linked_list);
+
+        linked_list.splice(0, DoublyLinkedList::from_iter(vec![-1, 0])); // Front
+        can_walk_forward_and_back(&linked_list);
+
+        linked_list.splice(7, DoublyLinkedList::from_iter(vec![6])); // Back
+        linked_list.splice(3, DoublyLinkedList::new()); // Nothing
+        can_walk_forward_and_back(&linked_list);
+
+        assert_eq!(linked_list.len(), 8);
+        assert_eq!(linked_list.to_vec(), vec![-1, 0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn can_split_off_and_append_back_together() {
+        let mut linked_list = DoublyLinkedList::from_iter(0..100);
+
+        for at in [0, 1, 50, 99, 100] {
+            let mut back = linked_list.split_off(at);
+            assert_eq!(linked_list.len() + back.len(), 100);
+            linked_list.append(&mut back);
+            can_walk_forward_and_back(&linked_list);
+        }
+
+        assert!(linked_list.iter().copied().eq(0..100));
+    }
+
+    #[test]
+    fn can_get_by_handle() {
+        let mut linked_list = DoublyLinkedList::new();
+        let one = linked_list.push_back(1);
+        let two = linked_list.push_back(2);
+        let zero = linked_list.push_front(0);
+
+        assert_eq!(linked_list.get_by_handle(zero), Some(&0));
+        assert_eq!(linked_list.get_by_handle(one), Some(&1));
+        assert_eq!(linked_list.get_by_handle(two), Some(&2));
+
+        *linked_list.get_mut_by_handle(one).unwrap() = 10;
+        assert_eq!(linked_list.to_vec(), vec![0, 10, 2]);
+    }
+
+    #[test]
+    fn can_move_by_handle() {
+        let mut linked_list = DoublyLinkedList::new();
+        let handles: Vec<_> = (1..=5).map(|n| linked_list.push_back(n)).collect();
+
+        assert!(linked_list.move_to_front(handles[2])); // [3, 1, 2, 4, 5]
+        can_walk_forward_and_back(&linked_list);
+        assert!(linked_list.move_to_front(handles[2])); // Already at the front
+
+        assert!(linked_list.move_to_back(handles[0])); // [3, 2, 4, 5, 1]
+        assert!(linked_list.move_to_back(handles[0])); // Already at the back
+        assert!(linked_list.move_to_front(handles[4])); // [5, 3, 2, 4, 1]
+        can_walk_forward_and_back(&linked_list);
+
+        assert_eq!(linked_list.len(), 5);
+        assert_eq!(linked_list.to_vec(), vec![5, 3, 2, 4, 1]);
+    }
+
+    #[test]
+    fn can_remove_by_handle() {
+        let mut linked_list = DoublyLinkedList::new();
+        let handles: Vec<_> = (1..=5).map(|n| linked_list.push_back(n)).collect();
+
+        assert_eq!(linked_list.remove_by_handle(handles[0]), Some(1)); // Head
+        assert_eq!(linked_list.remove_by_handle(handles[4]), Some(5)); // Tail
+        assert_eq!(linked_list.remove_by_handle(handles[2]), Some(3)); // Middle
+        can_walk_forward_and_back(&linked_list);
+
+        assert_eq!(linked_list.len(), 2);
+        assert_eq!(linked_list.to_vec(), vec![2, 4]);
+    }
+
+    #[test]
+    fn can_reject_stale_handles() {
+        let mut linked_list = DoublyLinkedList::new();
+        let one = linked_list.push_back(1);
+        let two = linked_list.push_back(2);
+
+        assert_eq!(linked_list.remove_by_handle(one), Some(1));
+        assert!(!linked_list.contains_handle(one));
+        assert_eq!(linked_list.remove_by_handle(one), None);
+        assert!(!linked_list.move_to_front(one));
+
+        // The freed slot gets reused without reviving the old handle
+        let three = linked_list.push_back(3);
+        assert_eq!(linked_list.get_by_handle(one), None);
+        assert_eq!(linked_list.get_by_handle(three), Some(&3));
+
+        // Removing through other methods invalidates handles as well
+        assert_eq!(linked_list.pop_front(), Some(2));
+        assert_eq!(linked_list.get_by_handle(two), None);
+
+        linked_list.clear();
+        assert_eq!(linked_list.get_by_handle(three), None);
+    }
+
+    #[test]
+    fn can_reject_handles_of_other_lists() {
+        let mut linked_list = DoublyLinkedList::new();
+        let mut other = DoublyLinkedList::new();
+
+        let handle = linked_list.push_back(1);
+        other.push_back(2);
+
+        assert!(linked_list.contains_handle(handle));
+        assert!(!other.contains_handle(handle));
+        assert_eq!(other.remove_by_handle(handle), None);
+        assert_eq!(other.len(), 1);
+    }
+
+    #[test]
+    fn can_invalidate_handles_when_moving_nodes_between_lists() {
+        let mut linked_list = DoublyLinkedList::new();
+        let mut other = DoublyLinkedList::new();
+
+        let one = linked_list.push_back(1);
+        let two = other.push_back(2);
+
+        // Only the handles of the moved nodes go stale
+        linked_list.append(&mut other);
+        assert_eq!(linked_list.get_by_handle(one), Some(&1));
+        assert!(!linked_list.contains_handle(two));
+        assert!(!other.contains_handle(two));
+
+        // New handles work as usual and nodes without a slot are freed normally
+        let three = linked_list.push_back(3);
+        assert_eq!(linked_list.pop_front(), Some(1));
+        assert!(!linked_list.contains_handle(one));
+        assert_eq!(linked_list.get_by_handle(three), Some(&3));
+
+        let back = linked_list.split_off(1);
+        assert!(!linked_list.contains_handle(three));
+        assert_eq!(back.to_vec(), vec![3]);
+        assert_eq!(linked_list.to_vec(), vec![2]);
+    }
+
+    #[test]
+    fn can_keep_handles_of_nodes_that_stay() {
+        let mut linked_list = DoublyLinkedList::new();
+        let handles: Vec<_> = (0..4).map(|n| linked_list.push_back(n)).collect();
+
+        let mut front = DoublyLinkedList::from_iter(vec![-2, -1]);
+        let moved = front.push_back(-3);
+        linked_list.prepend(&mut front);
+        linked_list.splice(2, DoublyLinkedList::from_iter(vec![10]));
+        assert_eq!(
+            linked_list.iter().copied().collect::<Vec<_>>(),
+            vec![-2, -1, 10, -3, 0, 1, 2, 3]
+        );
+        assert!(!linked_list.contains_handle(moved));
+
+        // Splitting only invalidates the handles of the nodes that were split off
+        let back = linked_list.split_off(6);
+        assert_eq!(back.to_vec(), vec![2, 3]);
+        assert_eq!(linked_list.get_by_handle(handles[0]), Some(&0));
+        assert_eq!(linked_list.get_by_handle(handles[1]), Some(&1));
+        assert!(!linked_list.contains_handle(handles[2]));
+        assert!(!linked_list.contains_handle(handles[3]));
+
+        let mut cursor = linked_list.cursor_front_mut();
+        cursor.move_next();
+        let front = cursor.split_before();
+        assert_eq!(front.to_vec(), vec![-2]);
+        assert!(linked_list.move_to_front(handles[1]));
+        assert_eq!(linked_list.remove_by_handle(handles[0]), Some(0));
+        assert_eq!(linked_list.to_vec(), vec![1, -1, 10, -3]);
+    }
+
+    #[test]
+    fn can_reissue_handles_with_cursors() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 3]);
+        let mut other = DoublyLinkedList::from_iter(vec![4, 5]);
+        linked_list.append(&mut other);
+
+        let mut cursor = linked_list.cursor_front_mut();
+        assert!(cursor.handle().is_some());
+        let two = cursor.insert_after(2);
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        let four = cursor.handle().unwrap();
+        assert_eq!(cursor.handle(), Some(four));
+        let zero = cursor.insert_before(0);
+
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.handle(), None);
+
+        assert_eq!(linked_list.get_by_handle(four), Some(&4));
+        assert!(linked_list.move_to_back(four));
+        assert_eq!(linked_list.remove_by_handle(two), Some(2));
+        assert_eq!(linked_list.remove_by_handle(zero), Some(0));
+        assert_eq!(linked_list.to_vec(), vec![1, 3, 5, 4]);
+    }
+
+    #[test]
+    fn can_sort() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![5, 1, 4, 2, 3, 2, 0]);
+        linked_list.sort();
+
+        can_walk_forward_and_back(&linked_list);
+        assert_eq!(linked_list.to_vec(), vec![0, 1, 2, 2, 3, 4, 5]);
+
+        let mut linked_list: DoublyLinkedList<i32> = DoublyLinkedList::new();
+        linked_list.sort();
+        assert_eq!(linked_list.len(), 0);
+
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1]);
+        linked_list.sort();
+        assert_eq!(linked_list.to_vec(), vec![1]);
+    }
+
+    #[test]
+    fn can_sort_by_and_sort_by_key_stably() {
+        let mut linked_list = DoublyLinkedList::from_iter(vec![1, 2, 3, 4, 5, 6, 7]);
+        linked_list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(
+            linked_list.iter().copied().collect::<Vec<_>>(),
+            vec![7, 6, 5, 4, 3, 2, 1]
+        );
+
+        let pairs = vec![(2, 'a'), (1, 'b'), (2, 'c'), (0, 'd'), (1, 'e'), (2, 'f')];
+        let mut linked_list = DoublyLinkedList::from_iter(pairs);
+        linked_list.sort_by_key(|pair| pair.0);
+
+        can_walk_forward_and_back(&linked_list);
+        assert_eq!(
+            linked_list.to_vec(),
+            vec![(0, 'd'), (1, 'b'), (1, 'e'), (2, 'a'), (2, 'c'), (2, 'f')]
+        );
+    }
+
+    #[test]
+    fn can_keep_handles_when_sorting() {
+        let mut linked_list = DoublyLinkedList::new();
+        let three = linked_list.push_back(3);
+        let one = linked_list.push_back(1);
+        let two = linked_list.push_back(2);
+
+        linked_list.sort();
+
+        assert_eq!(linked_list.get_by_handle(one), Some(&1));
+        assert_eq!(linked_list.get_by_handle(two), Some(&2));
+        assert!(linked_list.move_to_front(three));
+        assert_eq!(linked_list.to_vec(), vec![3, 1, 2]);
+    }
+
+    /// These tests exercise every unsafe operation of `DoublyLinkedList` with values that
+    /// own heap memory and are meant to be run under Miri as well with
+    /// `cargo +nightly miri test unsafe_doubly_linked_list`.
+    mod miri {
+        use super::*;
+        use crate::test_utils::DropCounter;
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use std::thread;
+
+        fn list_of(
+            values: impl IntoIterator<Item = i32>,
+            counter: &Rc<Cell<usize>>,
+        ) -> DoublyLinkedList<DropCounter> {
+            DoublyLinkedList::from_iter(values.into_iter().map(|n| DropCounter::new(n, counter)))
+        }
+
+        #[test]
+        fn can_sort_without_moving_values() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list = list_of([4, 2, 5, 1, 3], &counter);
+
+            linked_list.sort_by_key(|n| *n.value);
+            assert_eq!(counter.get(), 0);
+
+            can_walk_forward_and_back(&linked_list);
+            assert_eq!(values_of(&linked_list), vec![1, 2, 3, 4, 5]);
+
+            drop(linked_list);
+            assert_eq!(counter.get(), 5);
+        }
+
+        #[test]
+        fn can_restore_order_when_compare_panics() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list = list_of([4, 2, 5, 1, 3], &counter);
+            let handle = linked_list.push_back(DropCounter::new(0, &counter));
+
+            let mut comparisons = 0;
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                linked_list.sort_by(|a, b| {
+                    comparisons += 1;
+                    if comparisons == 4 {
+                        panic!("comparison failed");
+                    }
+                    a.value.cmp(&b.value)
+                });
+            }));
+
+            assert!(result.is_err());
+            can_walk_forward_and_back(&linked_list);
+            assert_eq!(values_of(&linked_list), vec![4, 2, 5, 1, 3, 0]);
+            assert!(linked_list.contains_handle(handle));
+            assert_eq!(counter.get(), 0);
+
+            drop(linked_list);
+            assert_eq!(counter.get(), 6);
+        }
+
+        fn values_of<A: Allocator>(linked_list: &DoublyLinkedList<DropCounter, A>) -> Vec<i32> {
+            linked_list.iter().map(|item| *item.value).collect()
+        }
+
+        #[test]
+        fn can_push_pop_insert_and_remove() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list = list_of([2, 3], &counter);
+
+            linked_list.push_front(DropCounter::new(1, &counter));
+            linked_list.push_back(DropCounter::new(5, &counter));
+            linked_list.insert(3, DropCounter::new(4, &counter));
+            linked_list.insert(0, DropCounter::new(0, &counter));
+            can_walk_forward_and_back(&linked_list);
+            assert_eq!(values_of(&linked_list), vec![0, 1, 2, 3, 4, 5]);
+
+            drop(linked_list.pop_front());
+            drop(linked_list.pop_back());
+            drop(linked_list.remove(1));
+            can_walk_forward_and_back(&linked_list);
+            assert_eq!(counter.get(), 3);
+            assert_eq!(values_of(&linked_list), vec![1, 3, 4]);
+
+            drop(linked_list);
+            assert_eq!(counter.get(), 6);
+        }
+
+        #[test]
+        fn can_get_mut_and_iter_mut() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list = list_of([1, 2, 3], &counter);
+
+            *linked_list.get_mut(1).unwrap().value += 10;
+            *linked_list.front_mut().unwrap().value += 10;
+            *linked_list.back_mut().unwrap().value += 10;
+            for item in linked_list.iter_mut().rev() {
+                *item.value *= 2;
+            }
+
+            assert_eq!(values_of(&linked_list), vec![22, 24, 26]);
+            assert_eq!(*linked_list.get(2).unwrap().value, 26);
+            assert_eq!(counter.get(), 0);
+        }
+
+        #[test]
+        fn can_drop_partially_consumed_iterators() {
+            let counter = Rc::new(Cell::new(0));
+            let linked_list = list_of(0..6, &counter);
+
+            let mut into_iter = linked_list.into_iter();
+            drop(into_iter.next());
+            drop(into_iter.next_back());
+            assert_eq!(counter.get(), 2);
+
+            drop(into_iter);
+            assert_eq!(counter.get(), 6);
+
+            let linked_list = list_of(0..3, &counter);
+            let values: Vec<_> = linked_list.to_vec();
+            assert_eq!(counter.get(), 6);
+            drop(values);
+            assert_eq!(counter.get(), 9);
+        }
+
+        #[test]
+        fn can_edit_with_cursor() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list = list_of(0..5, &counter);
+            let mut cursor = linked_list.cursor_front_mut();
+
+            cursor.move_next();
+            drop(cursor.remove_current());
+            cursor.insert_before(DropCounter::new(10, &counter));
+            cursor.insert_after(DropCounter::new(11, &counter));
+            cursor.splice_after(list_of([20, 21], &counter));
+            let mut after = cursor.split_after();
+            let before = cursor.split_before();
+
+            assert_eq!(values_of(&before), vec![0, 10]);
+            assert_eq!(values_of(&linked_list), vec![2]);
+            assert_eq!(values_of(&after), vec![20, 21, 11, 3, 4]);
+            assert_eq!(counter.get(), 1);
+
+            linked_list.append(&mut after);
+            linked_list.splice(1, before);
+            let back = linked_list.split_off(3);
+            linked_list.prepend(&mut list_of([-1], &counter));
+            can_walk_forward_and_back(&linked_list);
+            can_walk_forward_and_back(&back);
+            assert_eq!(values_of(&linked_list), vec![-1, 2, 0, 10]);
+            assert_eq!(values_of(&back), vec![20, 21, 11, 3, 4]);
+
+            drop(linked_list);
+            drop(back);
+            assert_eq!(counter.get(), 10);
+        }
+
+        #[test]
+        fn can_use_handles() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list = DoublyLinkedList::new();
+
+            let handles: Vec<_> = (0..5)
+                .map(|n| linked_list.push_back(DropCounter::new(n, &counter)))
+                .collect();
+
+            linked_list.move_to_front(handles[3]);
+            linked_list.move_to_back(handles[0]);
+            drop(linked_list.remove_by_handle(handles[2]));
+            *linked_list.get_mut_by_handle(handles[1]).unwrap().value += 10;
+
+            can_walk_forward_and_back(&linked_list);
+            assert_eq!(values_of(&linked_list), vec![3, 11, 4, 0]);
+            assert_eq!(linked_list.remove_by_handle(handles[2]), None);
+            assert_eq!(counter.get(), 1);
+
+            drop(linked_list);
+            assert_eq!(counter.get(), 5);
+        }
+
+        #[test]
+        fn can_use_arena_allocator() {
+            let counter = Rc::new(Cell::new(0));
+            let arena = BumpAllocator::with_chunk_size(64);
+
+            {
+                let mut linked_list = DoublyLinkedList::new_in(&arena);
+                let mut other = DoublyLinkedList::new_in(&arena);
+                for n in 0..10 {
+                    linked_list.push_back(DropCounter::new(n, &counter));
+                    other.push_front(DropCounter::new(n, &counter));
+                }
+
+                drop(linked_list.remove(5));
+                linked_list.append(&mut other);
+                let back = linked_list.split_off(15);
+                assert_eq!(linked_list.len() + back.len(), 19);
+            }
+
+            assert_eq!(counter.get(), 20);
+        }
+
+        #[test]
+        fn can_share_between_threads() {
+            let mut linked_list = DoublyLinkedList::from_iter((0..10).map(Box::new));
+
+            thread::scope(|scope| {
+                let handles: Vec<_> = (0..2)
+                    .map(|_| scope.spawn(|| linked_list.iter().map(|n| **n).sum::<i32>()))
+                    .collect();
+
+                for handle in handles {
+                    assert_eq!(handle.join().unwrap(), 45);
+                }
+            });
+
+            thread::scope(|scope| {
+                scope.spawn(|| {
+                    for n in linked_list.iter_mut() {
+                        **n += 1;
+                    }
+                });
+            });
+
+            let linked_list = thread::spawn(move || {
+                linked_list.push_back(Box::new(11));
+                linked_list
+            })
+            .join()
+            .unwrap();
+
+            assert_eq!(linked_list.iter().map(|n| **n).sum::<i32>(), 66);
+        }
+
+        #[test]
+        fn can_be_send_and_sync() {
+            fn assert_send_and_sync<T: Send + Sync>() {}
+
+            assert_send_and_sync::<DoublyLinkedList<i32>>();
+            assert_send_and_sync::<IntoIter<i32>>();
+            assert_send_and_sync::<Iter<'_, i32>>();
+            assert_send_and_sync::<IterMut<'_, i32>>();
+            assert_send_and_sync::<Cursor<'_, i32>>();
+            assert_send_and_sync::<CursorMut<'_, i32>>();
+            assert_send_and_sync::<NodeHandle>();
+        }
+    }
+
     /// Checks the integrity of all pointers in the linked list
     /// by walking from `self.head` to `self.tail` and ensuring that
     /// the value in all nodes match between each pass
-    fn can_walk_forward_and_back<T>(linked_list: &DoublyLinkedList<T>)
+    fn can_walk_forward_and_back<T, A>(linked_list: &DoublyLinkedList<T, A>)
     where
         T: Debug + PartialEq,
+        A: Allocator,
     {
         let mut values = Vec::with_capacity(linked_list.len);
 
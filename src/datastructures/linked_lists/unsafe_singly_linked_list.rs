@@ -113,7 +113,7 @@ impl<T: Debug> SinglyLinkedList<T> {
         }
     }
 
-    pub fn get_mut(&self, index: usize) -> Option<&mut T> {
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
         match self.get_node(index) {
             Some(node_ptr) => unsafe { Some(&mut (*node_ptr.as_ptr()).value) },
             None => None,
@@ -287,6 +287,11 @@ impl<T: Debug> Debug for SinglyLinkedList<T> {
     }
 }
 
+// The list owns its nodes like a `Box<Node<T>>` would, so it is as thread safe as the
+// values it holds
+unsafe impl<T: Send> Send for SinglyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for SinglyLinkedList<T> {}
+
 impl<T> Drop for SinglyLinkedList<T> {
     fn drop(&mut self) {
         while self.pop_front_node().is_some() {}
@@ -334,7 +339,7 @@ mod test {
     #[test]
     fn can_get_and_mutate_value_by_index() {
         let vec = vec![1, 2, 3, 4, 5, 6, 7];
-        let linked_list = SinglyLinkedList::from_iter(vec.clone());
+        let mut linked_list = SinglyLinkedList::from_iter(vec.clone());
 
         for idx in 0..linked_list.len() {
             *linked_list.get_mut(idx).unwrap() += 1;
@@ -423,4 +428,111 @@ mod test {
         assert_eq!(linked_list.back(), None);
         assert_eq!(linked_list.len(), 0);
     }
+
+    /// These tests exercise every unsafe operation of `SinglyLinkedList` with values that
+    /// own heap memory and are meant to be run under Miri as well with
+    /// `cargo +nightly miri test unsafe_singly_linked_list`.
+    mod miri {
+        use super::*;
+        use crate::test_utils::DropCounter;
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use std::thread;
+
+        fn values_of(linked_list: &SinglyLinkedList<DropCounter>) -> Vec<i32> {
+            (0..linked_list.len())
+                .map(|i| *linked_list.get(i).unwrap().value)
+                .collect()
+        }
+
+        #[test]
+        fn can_push_pop_insert_and_remove() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list = SinglyLinkedList::new();
+
+            linked_list.push_back(DropCounter::new(2, &counter));
+            linked_list.push_front(DropCounter::new(1, &counter));
+            linked_list.push_back(DropCounter::new(5, &counter));
+            linked_list.insert(2, DropCounter::new(3, &counter));
+            linked_list.insert(3, DropCounter::new(4, &counter));
+            linked_list.insert(0, DropCounter::new(0, &counter));
+            assert_eq!(values_of(&linked_list), vec![0, 1, 2, 3, 4, 5]);
+
+            drop(linked_list.pop_front());
+            drop(linked_list.pop_back());
+            drop(linked_list.remove(1));
+            drop(linked_list.remove(2));
+            assert_eq!(counter.get(), 4);
+            assert_eq!(values_of(&linked_list), vec![1, 3]);
+            assert_eq!(*linked_list.back().unwrap().value, 3);
+
+            drop(linked_list);
+            assert_eq!(counter.get(), 6);
+        }
+
+        #[test]
+        fn can_get_mut() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list =
+                SinglyLinkedList::from_iter((1..=3).map(|n| DropCounter::new(n, &counter)));
+
+            *linked_list.get_mut(1).unwrap().value += 10;
+            *linked_list.front_mut().unwrap().value += 10;
+            *linked_list.back_mut().unwrap().value += 10;
+
+            assert_eq!(values_of(&linked_list), vec![11, 12, 13]);
+            assert_eq!(counter.get(), 0);
+        }
+
+        #[test]
+        fn can_clear_and_to_vec() {
+            let counter = Rc::new(Cell::new(0));
+            let mut linked_list =
+                SinglyLinkedList::from_iter((0..5).map(|n| DropCounter::new(n, &counter)));
+
+            linked_list.clear();
+            assert_eq!(counter.get(), 5);
+
+            // The list is still usable after being cleared
+            linked_list.push_back(DropCounter::new(1, &counter));
+            linked_list.push_back(DropCounter::new(2, &counter));
+            let vec = linked_list.to_vec();
+            assert_eq!(counter.get(), 5);
+
+            drop(vec);
+            assert_eq!(counter.get(), 7);
+        }
+
+        #[test]
+        fn can_share_between_threads() {
+            let mut linked_list = SinglyLinkedList::from_iter((0..10).map(Box::new));
+
+            thread::scope(|scope| {
+                for _ in 0..2 {
+                    scope.spawn(|| {
+                        let sum: i32 = (0..linked_list.len())
+                            .map(|i| **linked_list.get(i).unwrap())
+                            .sum();
+                        assert_eq!(sum, 45);
+                    });
+                }
+            });
+
+            let linked_list = thread::spawn(move || {
+                **linked_list.get_mut(0).unwrap() = 10;
+                linked_list
+            })
+            .join()
+            .unwrap();
+
+            assert_eq!(**linked_list.front().unwrap(), 10);
+        }
+
+        #[test]
+        fn can_be_send_and_sync() {
+            fn assert_send_and_sync<T: Send + Sync>() {}
+
+            assert_send_and_sync::<SinglyLinkedList<i32>>();
+        }
+    }
 }
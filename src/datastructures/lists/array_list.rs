@@ -1,36 +1,31 @@
 use std::{
     alloc::{self, Layout},
+    cmp::Ordering,
+    mem,
     ops::{Index, IndexMut},
     ptr::{self, NonNull},
 };
 
+use crate::algorithms::searching::binary_search;
+use crate::algorithms::sorting::quick_sort;
+use crate::allocators::{Allocator, Global};
+
 const INITIAL_CAPACITY: usize = 4;
 
-pub struct ArrayList<T> {
+pub struct ArrayList<T, A: Allocator = Global> {
     ptr: NonNull<T>,
     capacity: usize,
     len: usize,
+    alloc: A,
 }
 
 impl<T> ArrayList<T> {
     pub fn new() -> Self {
-        Self {
-            ptr: NonNull::dangling(),
-            capacity: 0,
-            len: 0,
-        }
+        Self::new_in(Global)
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
-        let layout = Layout::array::<T>(capacity).expect("Out of memory");
-        let ptr = unsafe { alloc::alloc(layout.clone()) as *mut T };
-        let ptr = NonNull::new(ptr).expect("Failed to allocate memory");
-
-        Self {
-            ptr,
-            capacity,
-            len: 0,
-        }
+        Self::with_capacity_in(capacity, Global)
     }
 
     pub fn from_iter<I>(iter: I) -> Self
@@ -46,6 +41,45 @@ impl<T> ArrayList<T> {
 
         return list;
     }
+}
+
+impl<T, A: Allocator> ArrayList<T, A> {
+    /// Zero-sized types never need any backing memory, so a list of them
+    /// never allocates and has an effectively unlimited capacity.
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            capacity: if Self::IS_ZST { usize::MAX } else { 0 },
+            len: 0,
+            alloc,
+        }
+    }
+
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        // Allocating with a zero sized layout is undefined behaviour
+        if Self::IS_ZST || capacity == 0 {
+            return Self::new_in(alloc);
+        }
+
+        let layout = Layout::array::<T>(capacity).expect("Capacity overflow");
+        let ptr = match alloc.allocate(layout) {
+            Ok(ptr) => ptr.cast::<T>(),
+            Err(_) => alloc::handle_alloc_error(layout),
+        };
+
+        Self {
+            ptr,
+            capacity,
+            len: 0,
+            alloc,
+        }
+    }
+
+    pub fn allocator(&self) -> &A {
+        return &self.alloc;
+    }
 
     pub fn push(&mut self, value: T) {
         // This will catch the case where len and capcity are both 0
@@ -71,6 +105,10 @@ impl<T> ArrayList<T> {
     }
 
     pub fn insert(&mut self, index: usize, value: T) {
+        if index > self.len {
+            panic!("Index out of bounds. Len: {}, Got: {}.", self.len, index);
+        }
+
         if self.len >= self.capacity {
             self.grow();
         }
@@ -149,55 +187,172 @@ impl<T> ArrayList<T> {
             }
         }
     }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { &mut *ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Searches the sorted list with a `compare` function that returns the ordering of an
+    /// element relative to the target.
+    ///
+    /// Returns `Ok` with the index of a matching element, or `Err` with the index where
+    /// the target could be inserted while keeping the list sorted.
+    pub fn binary_search_by<F>(&self, compare: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        return binary_search::binary_search_by(self.as_slice(), compare);
+    }
+
+    /// Inserts `value` into a list that is sorted by `compare` and returns the index it
+    /// was inserted at. Equal elements keep their insertion order.
+    pub fn insert_sorted_by<F>(&mut self, value: T, mut compare: F) -> usize
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // Never report a match so that the search always lands after any equal elements
+        let index = match self.binary_search_by(|element| match compare(element, &value) {
+            Ordering::Equal => Ordering::Less,
+            ordering => ordering,
+        }) {
+            Ok(index) | Err(index) => index,
+        };
+
+        self.insert(index, value);
+        return index;
+    }
+
+    /// Sorts the list in place with `compare` using the crate's quick sort.
+    ///
+    /// NOTE: This sort is not stable.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        quick_sort::quick_sort_by(self.as_mut_slice(), |a, b| compare(a, b) == Ordering::Less);
+    }
+
+    /// Removes consecutive elements for which `same_bucket(current, previous)` returns
+    /// `true`, keeping the first element of every run.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        if self.len <= 1 {
+            return;
+        }
+
+        let len = self.len;
+        // If `same_bucket` or a destructor panics the remaining elements get leaked
+        // instead of being dropped twice
+        self.len = 0;
+
+        unsafe {
+            let ptr = self.ptr.as_ptr();
+            let mut write = 1;
+
+            for read in 1..len {
+                let current = ptr.add(read);
+                let previous = ptr.add(write - 1);
+
+                if same_bucket(&mut *current, &mut *previous) {
+                    ptr::drop_in_place(current);
+                } else {
+                    ptr::copy(current, ptr.add(write), 1);
+                    write += 1;
+                }
+            }
+
+            self.len = write;
+        }
+    }
+
+    /// Removes consecutive elements that map to the same key
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|current, previous| key(current) == key(previous));
+    }
 }
 
-impl<T> ArrayList<T> {
+impl<T: Ord, A: Allocator> ArrayList<T, A> {
+    /// Searches the sorted list for `target`.
+    ///
+    /// Returns `Ok` with the index of a matching element, or `Err` with the index where
+    /// `target` could be inserted while keeping the list sorted.
+    pub fn binary_search(&self, target: &T) -> Result<usize, usize> {
+        return binary_search::binary_search(self.as_slice(), target);
+    }
+
+    /// Inserts `value` into a sorted list and returns the index it was inserted at
+    pub fn insert_sorted(&mut self, value: T) -> usize {
+        return self.insert_sorted_by(value, T::cmp);
+    }
+
+    /// Sorts the list in place using the crate's quick sort.
+    ///
+    /// NOTE: This sort is not stable.
+    pub fn sort(&mut self) {
+        quick_sort::quick_sort(self.as_mut_slice());
+    }
+}
+
+impl<T: PartialEq, A: Allocator> ArrayList<T, A> {
+    /// Removes consecutive repeated elements
+    pub fn dedup(&mut self) {
+        self.dedup_by(|current, previous| current == previous);
+    }
+}
+
+impl<T, A: Allocator> ArrayList<T, A> {
     fn grow(&mut self) {
-        let (new_capacity, new_layout) = if self.capacity == 0 {
-            (
-                INITIAL_CAPACITY,
-                Layout::array::<T>(INITIAL_CAPACITY).unwrap(),
-            )
+        // The capacity of a ZST list is already `usize::MAX`, so needing to grow
+        // means that the length would overflow
+        if Self::IS_ZST {
+            panic!("Capacity overflow");
+        }
+
+        let new_capacity = if self.capacity == 0 {
+            INITIAL_CAPACITY
         } else {
-            let new_capacity = self.capacity * 2;
-            let new_layout = Layout::array::<T>(new_capacity).unwrap();
-            (new_capacity, new_layout)
+            self.capacity.checked_mul(2).expect("Capacity overflow")
         };
+        let new_layout = Layout::array::<T>(new_capacity).expect("Capacity overflow");
 
         let new_ptr = if self.capacity == 0 {
-            unsafe { alloc::alloc(new_layout) as *mut T }
+            self.alloc.allocate(new_layout)
         } else {
             let old_layout = Layout::array::<T>(self.capacity).unwrap();
-            let old_ptr = self.ptr.as_ptr() as *mut u8;
-            unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) as *mut T }
+            unsafe { self.alloc.grow(self.ptr.cast(), old_layout, new_layout) }
         };
 
-        self.ptr = match NonNull::new(new_ptr) {
-            Some(ptr) => ptr,
-            None => alloc::handle_alloc_error(new_layout),
+        self.ptr = match new_ptr {
+            Ok(ptr) => ptr.cast::<T>(),
+            Err(_) => alloc::handle_alloc_error(new_layout),
         };
         self.capacity = new_capacity;
     }
 }
 
-impl<T> Drop for ArrayList<T> {
+impl<T, A: Allocator> Drop for ArrayList<T, A> {
     fn drop(&mut self) {
         unsafe {
-            // Ensure that the `ptr` is not dangling by only dropping if
-            // capacity is greater than 0
-            if self.capacity > 0 {
-                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len));
-
-                alloc::dealloc(
-                    self.ptr.as_ptr() as *mut u8,
-                    Layout::array::<T>(self.capacity).unwrap(),
-                );
+            // Dropping the elements is always valid, even for ZSTs where `ptr` is dangling
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len));
+
+            // Ensure that the `ptr` is not dangling by only deallocating if
+            // memory was actually allocated
+            if !Self::IS_ZST && self.capacity > 0 {
+                self.alloc
+                    .deallocate(self.ptr.cast(), Layout::array::<T>(self.capacity).unwrap());
             }
         }
     }
 }
 
-impl<T> Index<usize> for ArrayList<T> {
+impl<T, A: Allocator> Index<usize> for ArrayList<T, A> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -205,7 +360,7 @@ impl<T> Index<usize> for ArrayList<T> {
     }
 }
 
-impl<T> IndexMut<usize> for ArrayList<T> {
+impl<T, A: Allocator> IndexMut<usize> for ArrayList<T, A> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         self.get_mut(index).expect("index out of bounds")
     }
@@ -214,6 +369,7 @@ impl<T> IndexMut<usize> for ArrayList<T> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::allocators::BumpAllocator;
 
     #[test]
     fn can_push_and_pop() {
@@ -341,4 +497,281 @@ mod test {
         let mut list = ArrayList::from_iter([1, 2, 3]);
         list.remove(3);
     }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds. Len: 3, Got: 4.")]
+    fn can_panic_when_inserting_out_of_bounds() {
+        let mut list = ArrayList::from_iter([1, 2, 3]);
+        list.insert(4, 4);
+    }
+
+    #[test]
+    fn can_create_with_zero_capacity() {
+        let mut list = ArrayList::with_capacity(0);
+        assert_eq!(list.capacity(), 0);
+
+        list.push(1);
+        assert_eq!(list.as_slice(), &[1]);
+        assert_eq!(list.capacity(), 4);
+    }
+
+    #[test]
+    fn can_use_custom_allocator() {
+        let arena = BumpAllocator::with_chunk_size(64);
+        let mut list = ArrayList::new_in(&arena);
+
+        for n in 0..100 {
+            list.push(n.to_string());
+        }
+        list.insert(0, String::from("first"));
+        assert_eq!(list.remove(1), "0");
+
+        assert_eq!(list.len(), 100);
+        assert_eq!(list[0], "first");
+        assert_eq!(list[99], "99");
+        assert!(arena.allocated_bytes() > 0);
+    }
+
+    #[test]
+    fn can_binary_search() {
+        let list = ArrayList::from_iter([1, 3, 5, 7]);
+
+        assert_eq!(list.binary_search(&1), Ok(0));
+        assert_eq!(list.binary_search(&7), Ok(3));
+        assert_eq!(list.binary_search(&0), Err(0));
+        assert_eq!(list.binary_search(&4), Err(2));
+        assert_eq!(list.binary_search(&8), Err(4));
+
+        assert_eq!(list.binary_search_by(|n| n.cmp(&5)), Ok(2));
+        assert_eq!(ArrayList::<i32>::new().binary_search(&1), Err(0));
+    }
+
+    #[test]
+    fn can_insert_sorted() {
+        let mut list = ArrayList::new();
+
+        assert_eq!(list.insert_sorted(5), 0);
+        assert_eq!(list.insert_sorted(1), 0);
+        assert_eq!(list.insert_sorted(3), 1);
+        assert_eq!(list.insert_sorted(9), 3);
+        assert_eq!(list.insert_sorted(3), 2);
+
+        assert_eq!(list.as_slice(), &[1, 3, 3, 5, 9]);
+    }
+
+    #[test]
+    fn can_insert_sorted_by_after_equal_elements() {
+        let mut list = ArrayList::new();
+
+        list.insert_sorted_by((2, 'a'), |a, b| a.0.cmp(&b.0));
+        list.insert_sorted_by((1, 'b'), |a, b| a.0.cmp(&b.0));
+        list.insert_sorted_by((2, 'c'), |a, b| a.0.cmp(&b.0));
+        list.insert_sorted_by((1, 'd'), |a, b| a.0.cmp(&b.0));
+
+        assert_eq!(list.as_slice(), &[(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+    }
+
+    #[test]
+    fn can_dedup() {
+        let mut list = ArrayList::from_iter([1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        list.dedup();
+        assert_eq!(list.as_slice(), &[1, 2, 3, 1, 4]);
+
+        let mut list = ArrayList::<i32>::new();
+        list.dedup();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn can_dedup_by_key() {
+        let mut list = ArrayList::from_iter([10, 11, 20, 21, 22, 30, 12]);
+        list.dedup_by_key(|n| *n / 10);
+
+        assert_eq!(list.as_slice(), &[10, 20, 30, 12]);
+    }
+
+    #[test]
+    fn can_sort() {
+        let mut list = ArrayList::from_iter([5, 1, 9, 2, 7, 3, 3, 0]);
+        list.sort();
+        assert_eq!(list.as_slice(), &[0, 1, 2, 3, 3, 5, 7, 9]);
+
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(list.as_slice(), &[9, 7, 5, 3, 3, 2, 1, 0]);
+
+        let mut comparisons = 0;
+        list.sort_by(|a, b| {
+            comparisons += 1;
+            a.cmp(b)
+        });
+        assert_eq!(list.as_slice(), &[0, 1, 2, 3, 3, 5, 7, 9]);
+        assert!(comparisons > 0);
+    }
+
+    #[test]
+    fn can_sort_large_sorted_list() {
+        let mut list = ArrayList::from_iter(0..100_000);
+        list.sort();
+        assert!(list.as_slice().iter().copied().eq(0..100_000));
+
+        list.sort_by(|a, b| b.cmp(a));
+        assert!(list.as_slice().iter().copied().eq((0..100_000).rev()));
+    }
+
+    /// These tests exercise every unsafe code path of `ArrayList` and are meant to be
+    /// run under Miri as well with `cargo +nightly miri test array_list`.
+    mod miri {
+        use super::*;
+        use crate::test_utils::DropCounter;
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        #[derive(Debug, PartialEq)]
+        struct Marker;
+
+        /// A zero-sized type that still has drop glue
+        struct ZstDropCounter<'a>(&'a Cell<usize>);
+
+        impl Drop for ZstDropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        #[test]
+        fn can_push_and_pop_zst() {
+            let mut list = ArrayList::new();
+            assert_eq!(list.capacity(), usize::MAX);
+
+            for _ in 0..100 {
+                list.push(Marker);
+            }
+            assert_eq!(list.len(), 100);
+            assert_eq!(list.capacity(), usize::MAX);
+            assert_eq!(list.as_slice().len(), 100);
+
+            for _ in 0..100 {
+                assert_eq!(list.pop(), Some(Marker));
+            }
+            assert_eq!(list.pop(), None);
+            assert!(list.is_empty());
+        }
+
+        #[test]
+        fn can_insert_and_remove_zst() {
+            let mut list = ArrayList::with_capacity(10);
+            assert_eq!(list.capacity(), usize::MAX);
+
+            list.insert(0, ());
+            list.insert(1, ());
+            list.insert(0, ());
+            list.insert(2, ());
+            assert_eq!(list.len(), 4);
+            assert_eq!(list.get(3), Some(&()));
+            assert_eq!(list.get(4), None);
+
+            assert_eq!(list.remove(1), ());
+            assert_eq!(list.remove(2), ());
+            assert_eq!(list.len(), 2);
+
+            list.clear();
+            assert!(list.is_empty());
+        }
+
+        #[test]
+        fn can_drop_zst_with_drop_glue() {
+            let counter = Cell::new(0);
+
+            {
+                let mut list = ArrayList::new();
+                for _ in 0..10 {
+                    list.push(ZstDropCounter(&counter));
+                }
+
+                drop(list.pop());
+                drop(list.remove(3));
+                list.insert(0, ZstDropCounter(&counter));
+                assert_eq!(counter.get(), 2);
+            }
+
+            // 2 dropped manually + 9 dropped with the list
+            assert_eq!(counter.get(), 11);
+        }
+
+        #[test]
+        fn can_drop_every_element_exactly_once() {
+            let counter = Rc::new(Cell::new(0));
+
+            {
+                let mut list = ArrayList::new();
+                for id in 0..10 {
+                    list.push(DropCounter::new(id, &counter));
+                }
+
+                let popped = list.pop().unwrap();
+                assert_eq!(*popped.value, 9);
+                drop(popped);
+                assert_eq!(counter.get(), 1);
+
+                let removed = list.remove(0);
+                assert_eq!(*removed.value, 0);
+                drop(removed);
+                assert_eq!(counter.get(), 2);
+
+                // Forces a reallocation while holding values with drop glue
+                list.insert(4, DropCounter::new(10, &counter));
+                for id in 11..20 {
+                    list.push(DropCounter::new(id, &counter));
+                }
+                assert_eq!(list.len(), 18);
+                assert_eq!(*list[4].value, 10);
+                assert_eq!(counter.get(), 2);
+            }
+
+            // 2 dropped manually + 18 dropped with the list
+            assert_eq!(counter.get(), 20);
+        }
+
+        #[test]
+        fn can_clear_elements_with_drop() {
+            let counter = Rc::new(Cell::new(0));
+            let mut list = ArrayList::from_iter((0..5).map(|id| DropCounter::new(id, &counter)));
+
+            list.clear();
+            assert_eq!(counter.get(), 5);
+            assert!(list.is_empty());
+
+            list.push(DropCounter::new(5, &counter));
+            drop(list);
+            assert_eq!(counter.get(), 6);
+        }
+
+        #[test]
+        fn can_dedup_elements_with_drop() {
+            let counter = Rc::new(Cell::new(0));
+            let mut list =
+                ArrayList::from_iter([0, 0, 1, 1, 1, 2].map(|id| DropCounter::new(id, &counter)));
+
+            list.dedup_by_key(|item| *item.value);
+            assert_eq!(counter.get(), 3);
+            assert_eq!(list.len(), 3);
+            assert_eq!(*list[2].value, 2);
+
+            drop(list);
+            assert_eq!(counter.get(), 6);
+        }
+
+        #[test]
+        fn can_drop_empty_list_without_allocating() {
+            let list: ArrayList<String> = ArrayList::new();
+            drop(list);
+
+            let list: ArrayList<String> = ArrayList::with_capacity(0);
+            drop(list);
+
+            let list: ArrayList<String> = ArrayList::with_capacity(3);
+            assert_eq!(list.capacity(), 3);
+            drop(list);
+        }
+    }
 }
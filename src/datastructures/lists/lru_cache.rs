@@ -1,15 +1,14 @@
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::ptr::NonNull;
 
-use crate::datastructures::unsafe_doubly_linked_list::{DoublyLinkedList, Iter, Node};
+use crate::datastructures::unsafe_doubly_linked_list::{DoublyLinkedList, Iter, NodeHandle};
 
 pub struct LRUCache<'a, K, V>
 where
     K: Eq + PartialEq + Hash + Clone,
 {
     values: DoublyLinkedList<(&'a K, V)>,
-    map: HashMap<K, NonNull<Node<(&'a K, V)>>>,
+    map: HashMap<K, NodeHandle>,
     max_size: usize,
 }
 
@@ -27,25 +26,21 @@ where
     }
 
     pub fn get(&mut self, key: &K) -> Option<&V> {
-        if self.map.contains_key(key) {
-            let node = self.map.get(key).unwrap();
-            unsafe { self.values.unlink_node_unchecked(*node) };
-            self.values.push_front_node(*node);
+        if let Some(handle) = self.map.get(key) {
+            self.values.move_to_front(*handle);
 
-            return self.values.front().map(|(_, v)| v);
+            return self.values.get_by_handle(*handle).map(|(_, v)| v);
         } else {
             return None;
         }
     }
 
     pub fn set(&mut self, key: &'a K, value: V) {
-        if self.map.contains_key(key) {
-            unsafe {
-                let node = self.map.get(key).unwrap();
-                self.values.unlink_node_unchecked(*node);
-                self.values.push_front_node(*node);
+        if let Some(handle) = self.map.get(key) {
+            self.values.move_to_front(*handle);
 
-                (*node.as_ptr()).value.1 = value;
+            if let Some((_, old_value)) = self.values.get_mut_by_handle(*handle) {
+                *old_value = value;
             }
         } else {
             if self.size() >= self.max_size {
@@ -57,9 +52,8 @@ where
                 }
             }
 
-            let node = Node::new_as_ptr((key, value));
-            self.map.insert(key.clone(), node);
-            self.values.push_front_node(node);
+            let handle = self.values.push_front((key, value));
+            self.map.insert(key.clone(), handle);
         }
     }
 
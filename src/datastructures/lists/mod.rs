@@ -1,3 +1,5 @@
 pub mod array_list;
 pub mod lru_cache;
 pub mod ring_buffer;
+pub mod skip_list;
+pub mod small_array_list;
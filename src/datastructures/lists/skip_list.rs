@@ -0,0 +1,450 @@
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::ops::{Bound, RangeBounds};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::datastructures::arena::Arena;
+
+/// The highest level a node can reach. With a promotion chance of 1/2 this is plenty
+/// for any list that fits in memory.
+const MAX_LEVEL: usize = 32;
+
+/// An ordered map built from a probabilistic skip list. Every node is linked on level 0
+/// and on each level above with a chance of 1/2, which gives O(log n) expected time for
+/// `insert`, `remove` and `get`.
+///
+/// The nodes live in an arena and link to each other by index. The levels are picked by
+/// a `StdRng`, which can be seeded with `with_seed` to make the shape of the list
+/// reproducible.
+pub struct SkipList<K, V> {
+    nodes: Arena<Node<K, V>>,
+    /// Forward links of the sentinel head, one for every level currently in use
+    head: Vec<Option<usize>>,
+    len: usize,
+    rng: StdRng,
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// Forward links, one for every level the node is part of
+    next: Vec<Option<usize>>,
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Creates a list whose node levels are picked deterministically from `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    /// Inserts `value` under `key` and returns the value that was replaced, if any
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let prev = self.find_predecessors(&key);
+
+        if let Some(node) = self.next_of(prev[0], 0) {
+            let node = self.node_mut(node);
+            if node.key == key {
+                return Some(std::mem::replace(&mut node.value, value));
+            }
+        }
+
+        // `prev` already points at the head on any level that is new
+        let level = self.random_level();
+        while self.head.len() < level {
+            self.head.push(None);
+        }
+
+        let next = (0..level).map(|l| self.next_of(prev[l], l)).collect();
+        let new_node = self.allocate_node(Node { key, value, next });
+        for (l, prev) in prev.into_iter().enumerate().take(level) {
+            self.set_next(prev, l, Some(new_node));
+        }
+
+        self.len += 1;
+        return None;
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let prev = self.find_predecessors(key);
+
+        let target = self.next_of(prev[0], 0)?;
+        if self.node(target).key != *key {
+            return None;
+        }
+
+        let target_node = self.deallocate_node(target);
+        for (l, next) in target_node.next.into_iter().enumerate() {
+            self.set_next(prev[l], l, next);
+        }
+
+        // Drop the levels that no node reaches anymore
+        while let Some(None) = self.head.last() {
+            self.head.pop();
+        }
+
+        self.len -= 1;
+        return Some(target_node.value);
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let node = self.find_node(key)?;
+        return Some(&self.node(node).value);
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let node = self.find_node(key)?;
+        return Some(&mut self.node_mut(node).value);
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        return self.find_node(key).is_some();
+    }
+
+    /// Iterates over the entries whose keys fall within `range` in ascending order.
+    /// Finding both ends of the range takes O(log n) expected time.
+    pub fn range<R>(&self, range: R) -> Range<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let first = match range.start_bound() {
+            Bound::Included(start) => self.next_of(self.find_last(|key| key < start), 0),
+            Bound::Excluded(start) => self.next_of(self.find_last(|key| key <= start), 0),
+            Bound::Unbounded => self.next_of(None, 0),
+        };
+
+        let last = match range.end_bound() {
+            Bound::Included(end) => self.find_last(|key| key <= end),
+            Bound::Excluded(end) => self.find_last(|key| key < end),
+            Bound::Unbounded => self.find_last(|_| true),
+        };
+
+        // The range is empty if its ends passed each other
+        let (next_node, last_node) = match (first, last) {
+            (Some(first), Some(last)) if self.node(first).key <= self.node(last).key => {
+                (Some(first), Some(last))
+            }
+            _ => (None, None),
+        };
+
+        Range {
+            nodes: &self.nodes,
+            next_node,
+            last_node,
+        }
+    }
+
+    pub fn iter(&self) -> Range<'_, K, V> {
+        return self.range(..);
+    }
+}
+
+impl<K, V> SkipList<K, V> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len == 0;
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.head.clear();
+        self.len = 0;
+    }
+}
+
+// private helper functions
+impl<K: Ord, V> SkipList<K, V> {
+    fn with_rng(rng: StdRng) -> Self {
+        Self {
+            nodes: Arena::new(),
+            head: Vec::new(),
+            len: 0,
+            rng,
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        let mut level = 1;
+        while level < MAX_LEVEL && self.rng.gen_bool(0.5) {
+            level += 1;
+        }
+
+        return level;
+    }
+
+    fn node(&self, node: usize) -> &Node<K, V> {
+        self.nodes.get(node)
+    }
+
+    fn node_mut(&mut self, node: usize) -> &mut Node<K, V> {
+        self.nodes.get_mut(node)
+    }
+
+    /// Returns the successor on `level` of `node`, where `None` stands for the head.
+    /// The head has no successors on levels that are not in use yet.
+    fn next_of(&self, node: Option<usize>, level: usize) -> Option<usize> {
+        match node {
+            Some(node) => self.node(node).next[level],
+            None => self.head.get(level).copied().flatten(),
+        }
+    }
+
+    fn set_next(&mut self, node: Option<usize>, level: usize, next: Option<usize>) {
+        match node {
+            Some(node) => self.node_mut(node).next[level] = next,
+            None => self.head[level] = next,
+        }
+    }
+
+    /// Returns the last node whose key satisfies `pred`, or `None` for the head.
+    /// `pred` must hold for a prefix of the keys.
+    fn find_last<F>(&self, pred: F) -> Option<usize>
+    where
+        F: Fn(&K) -> bool,
+    {
+        let mut curr_node = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(next) = self.next_of(curr_node, level) {
+                if !pred(&self.node(next).key) {
+                    break;
+                }
+                curr_node = Some(next);
+            }
+        }
+
+        return curr_node;
+    }
+
+    /// Returns the last node before `key` on every level, where `None` stands for the head
+    fn find_predecessors(&self, key: &K) -> [Option<usize>; MAX_LEVEL] {
+        let mut prev = [None; MAX_LEVEL];
+
+        let mut curr_node = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(next) = self.next_of(curr_node, level) {
+                if self.node(next).key >= *key {
+                    break;
+                }
+                curr_node = Some(next);
+            }
+            prev[level] = curr_node;
+        }
+
+        return prev;
+    }
+
+    fn find_node(&self, key: &K) -> Option<usize> {
+        let node = self.next_of(self.find_last(|k| k < key), 0)?;
+
+        if self.node(node).key == *key {
+            return Some(node);
+        }
+
+        return None;
+    }
+
+    fn allocate_node(&mut self, node: Node<K, V>) -> usize {
+        return self.nodes.alloc(node);
+    }
+
+    fn deallocate_node(&mut self, node: usize) -> Node<K, V> {
+        return self.nodes.free(node);
+    }
+}
+
+impl<K: Ord, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Debug, V: Debug> Debug for SkipList<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a SkipList<K, V> {
+    type Item = (&'a K, &'a V);
+
+    type IntoIter = Range<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+pub struct Range<'a, K, V> {
+    nodes: &'a Arena<Node<K, V>>,
+    next_node: Option<usize>,
+    /// The last node to yield, reached by following the level 0 links from `next_node`
+    last_node: Option<usize>,
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr_node = self.next_node?;
+        let node = self.nodes.get(curr_node);
+
+        self.next_node = match self.last_node == Some(curr_node) {
+            true => None,
+            false => node.next[0],
+        };
+
+        return Some((&node.key, &node.value));
+    }
+}
+
+impl<K, V> FusedIterator for Range<'_, K, V> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn levels_of<K: Ord, V>(list: &SkipList<K, V>) -> Vec<usize> {
+        let mut levels = Vec::new();
+        let mut curr_node = list.next_of(None, 0);
+        while let Some(node) = curr_node {
+            levels.push(list.node(node).next.len());
+            curr_node = list.node(node).next[0];
+        }
+
+        return levels;
+    }
+
+    #[test]
+    fn can_insert_and_get() {
+        let mut list = SkipList::with_seed(0);
+        for n in [5, 1, 4, 2, 3] {
+            assert_eq!(list.insert(n, n * 10), None);
+        }
+
+        assert_eq!(list.len(), 5);
+        for n in 1..=5 {
+            assert_eq!(list.get(&n), Some(&(n * 10)));
+        }
+        assert_eq!(list.get(&0), None);
+        assert_eq!(list.get(&6), None);
+        assert!(list.contains_key(&3));
+        assert!(!list.contains_key(&7));
+
+        let keys: Vec<_> = list.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn can_replace_values() {
+        let mut list = SkipList::with_seed(0);
+        list.insert("a", 1);
+
+        assert_eq!(list.insert("a", 2), Some(1));
+        assert_eq!(list.len(), 1);
+
+        *list.get_mut(&"a").unwrap() += 1;
+        assert_eq!(list.get(&"a"), Some(&3));
+        assert_eq!(list.get_mut(&"b"), None);
+    }
+
+    #[test]
+    fn can_remove() {
+        let mut list = SkipList::with_seed(0);
+        for n in 0..10 {
+            list.insert(n, n);
+        }
+
+        assert_eq!(list.remove(&0), Some(0));
+        assert_eq!(list.remove(&9), Some(9));
+        assert_eq!(list.remove(&5), Some(5));
+        assert_eq!(list.remove(&5), None);
+        assert_eq!(list.remove(&42), None);
+        assert_eq!(list.len(), 7);
+
+        let keys: Vec<_> = list.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![1, 2, 3, 4, 6, 7, 8]);
+
+        for n in 0..10 {
+            list.remove(&n);
+        }
+        assert!(list.is_empty());
+        assert!(list.head.is_empty());
+        assert_eq!(list.nodes.capacity(), 0);
+    }
+
+    #[test]
+    fn can_iterate_over_ranges() {
+        let mut list = SkipList::with_seed(7);
+        for n in (0..20).step_by(2) {
+            list.insert(n, ());
+        }
+
+        let keys = |range: Range<i32, ()>| range.map(|(k, _)| *k).collect::<Vec<_>>();
+
+        assert_eq!(keys(list.range(4..10)), vec![4, 6, 8]);
+        assert_eq!(keys(list.range(4..=10)), vec![4, 6, 8, 10]);
+        assert_eq!(keys(list.range(3..9)), vec![4, 6, 8]);
+        assert_eq!(keys(list.range(..5)), vec![0, 2, 4]);
+        assert_eq!(keys(list.range(15..)), vec![16, 18]);
+        assert_eq!(
+            keys(list.range((Bound::Excluded(4), Bound::Excluded(10)))),
+            vec![6, 8]
+        );
+        assert_eq!(keys(list.range(..)).len(), 10);
+
+        assert_eq!(keys(list.range(5..6)), vec![]);
+        assert_eq!(keys(list.range(20..)), vec![]);
+        assert_eq!(keys(list.range(..0)), vec![]);
+        assert_eq!(keys(SkipList::<i32, ()>::with_seed(0).range(..)), vec![]);
+    }
+
+    #[test]
+    fn can_build_the_same_levels_from_the_same_seed() {
+        let mut a = SkipList::with_seed(42);
+        let mut b = SkipList::with_seed(42);
+        for n in 0..100 {
+            a.insert(n, ());
+            b.insert(n, ());
+        }
+
+        assert_eq!(levels_of(&a), levels_of(&b));
+        assert!(levels_of(&a).iter().any(|level| *level > 1));
+    }
+
+    #[test]
+    fn can_match_btree_map() {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let mut list = SkipList::with_seed(1234);
+        let mut map = BTreeMap::new();
+
+        for _ in 0..2000 {
+            let key = rng.gen_range(0..200);
+            match rng.gen_range(0..3) {
+                0 | 1 => assert_eq!(list.insert(key, key * 2), map.insert(key, key * 2)),
+                _ => assert_eq!(list.remove(&key), map.remove(&key)),
+            }
+            assert_eq!(list.len(), map.len());
+        }
+
+        assert!(list.iter().eq(map.iter()));
+        assert!(list.range(50..150).eq(map.range(50..150)));
+    }
+
+    #[test]
+    fn can_debug_print() {
+        let mut list = SkipList::with_seed(0);
+        list.insert(2, "b");
+        list.insert(1, "a");
+
+        assert_eq!(format!("{:?}", list), r#"{1: "a", 2: "b"}"#);
+    }
+}
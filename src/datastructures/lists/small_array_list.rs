@@ -0,0 +1,418 @@
+use std::{
+    mem::{self, MaybeUninit},
+    ops::{Index, IndexMut},
+    ptr,
+};
+
+use super::array_list::ArrayList;
+
+/// A list that stores up to `N` elements inline and only spills over to a heap allocated
+/// `ArrayList` when it outgrows its inline buffer. Once spilled, it grows exactly like an
+/// `ArrayList` does.
+pub struct SmallArrayList<T, const N: usize> {
+    storage: Storage<T, N>,
+}
+
+enum Storage<T, const N: usize> {
+    Inline {
+        buf: [MaybeUninit<T>; N],
+        len: usize,
+    },
+    Heap(ArrayList<T>),
+}
+
+impl<T, const N: usize> SmallArrayList<T, N> {
+    pub fn new() -> Self {
+        Self {
+            storage: Storage::Inline {
+                buf: [const { MaybeUninit::uninit() }; N],
+                len: 0,
+            },
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= N {
+            return Self::new();
+        }
+
+        Self {
+            storage: Storage::Heap(ArrayList::with_capacity(capacity)),
+        }
+    }
+
+    pub fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        let mut list = Self::new();
+
+        while let Some(item) = iter.next() {
+            list.push(item);
+        }
+
+        return list;
+    }
+
+    pub fn push(&mut self, value: T) {
+        match &mut self.storage {
+            Storage::Inline { buf, len } if *len < N => {
+                buf[*len].write(value);
+                *len += 1;
+            }
+            Storage::Inline { .. } => {
+                self.spill();
+                self.push(value);
+            }
+            Storage::Heap(list) => list.push(value),
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.storage {
+            Storage::Inline { buf, len } => {
+                if *len == 0 {
+                    return None;
+                }
+
+                *len -= 1;
+                Some(unsafe { buf[*len].assume_init_read() })
+            }
+            Storage::Heap(list) => list.pop(),
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        match &mut self.storage {
+            Storage::Inline { len, .. } if index > *len => {
+                panic!("Index out of bounds. Len: {}, Got: {}.", len, index);
+            }
+            Storage::Inline { buf, len } if *len < N => unsafe {
+                // Shift all elements down by one index
+                let src = buf.as_mut_ptr().add(index);
+                let dst = src.add(1);
+                let count = *len - index;
+                ptr::copy(src, dst, count);
+
+                (*src).write(value);
+                *len += 1;
+            },
+            Storage::Inline { .. } => {
+                self.spill();
+                self.insert(index, value);
+            }
+            Storage::Heap(list) => list.insert(index, value),
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        match &mut self.storage {
+            Storage::Inline { buf, len } => {
+                if index >= *len {
+                    panic!("Index out of bounds. Len: {}, Got: {}.", len, index);
+                }
+
+                unsafe {
+                    let ptr = buf.as_mut_ptr().add(index);
+                    let target_value = (*ptr).assume_init_read();
+
+                    let count = *len - index - 1;
+                    ptr::copy(ptr.add(1), ptr, count);
+
+                    *len -= 1;
+                    return target_value;
+                }
+            }
+            Storage::Heap(list) => list.remove(index),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        return self.as_slice().get(index);
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match &mut self.storage {
+            Storage::Inline { buf, len } => {
+                if index >= *len {
+                    return None;
+                }
+
+                Some(unsafe { buf[index].assume_init_mut() })
+            }
+            Storage::Heap(list) => list.get_mut(index),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { .. } => N,
+            Storage::Heap(list) => list.capacity(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { len, .. } => *len,
+            Storage::Heap(list) => list.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Returns `true` if the elements have been moved out of the inline buffer
+    /// onto the heap
+    pub fn spilled(&self) -> bool {
+        return matches!(self.storage, Storage::Heap(_));
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        match &self.storage {
+            Storage::Inline { buf, len } => unsafe {
+                &*ptr::slice_from_raw_parts(buf.as_ptr() as *const T, *len)
+            },
+            Storage::Heap(list) => list.as_slice(),
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.storage {
+            Storage::Inline { buf, len } => unsafe {
+                &mut *ptr::slice_from_raw_parts_mut(buf.as_mut_ptr() as *mut T, *len)
+            },
+            Storage::Heap(list) => list.as_mut_slice(),
+        }
+    }
+
+    /// Moves the elements from `at` onwards into a new list
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        if at > len {
+            panic!("Index out of bounds. Len: {}, Got: {}.", len, at);
+        }
+
+        let mut other = Self::with_capacity(len - at);
+        while self.len() > at {
+            other.push(self.pop().unwrap());
+        }
+        other.as_mut_slice().reverse();
+
+        return other;
+    }
+
+    /// Moves all of the elements of `other` to the back of this list, leaving `other` empty
+    pub fn append(&mut self, other: &mut Self) {
+        other.as_mut_slice().reverse();
+        while let Some(value) = other.pop() {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, const N: usize> SmallArrayList<T, N> {
+    /// Moves the inline elements into an `ArrayList` with double the inline capacity
+    fn spill(&mut self) {
+        let mut list = ArrayList::with_capacity((N * 2).max(1));
+
+        if let Storage::Inline { buf, len } = &mut self.storage {
+            // Take ownership of the elements before the inline storage is overwritten
+            // so that they are not dropped twice
+            let count = mem::replace(len, 0);
+            for slot in &buf[..count] {
+                list.push(unsafe { slot.assume_init_read() });
+            }
+        }
+
+        self.storage = Storage::Heap(list);
+    }
+}
+
+impl<T, const N: usize> Drop for SmallArrayList<T, N> {
+    fn drop(&mut self) {
+        // The heap variant is dropped by `ArrayList`
+        if let Storage::Inline { buf, len } = &mut self.storage {
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                    buf.as_mut_ptr() as *mut T,
+                    *len,
+                ));
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Index<usize> for SmallArrayList<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for SmallArrayList<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::DropCounter;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn can_push_and_pop_inline() {
+        let mut list: SmallArrayList<i32, 4> = SmallArrayList::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.push(4);
+
+        assert_eq!(list.as_slice(), &[1, 2, 3, 4]);
+        assert_eq!(list.capacity(), 4);
+        assert!(!list.spilled());
+
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn can_spill_to_heap() {
+        let mut list: SmallArrayList<i32, 2> = SmallArrayList::from_iter([1, 2]);
+        assert!(!list.spilled());
+
+        list.push(3);
+        assert!(list.spilled());
+        assert_eq!(list.capacity(), 4);
+        assert_eq!(list.as_slice(), &[1, 2, 3]);
+
+        list.push(4);
+        list.push(5);
+        assert_eq!(list.capacity(), 8);
+        assert_eq!(list.as_slice(), &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn can_create_with_capacity() {
+        let list: SmallArrayList<i32, 4> = SmallArrayList::with_capacity(3);
+        assert!(!list.spilled());
+        assert_eq!(list.capacity(), 4);
+
+        let list: SmallArrayList<i32, 4> = SmallArrayList::with_capacity(10);
+        assert!(list.spilled());
+        assert_eq!(list.capacity(), 10);
+    }
+
+    #[test]
+    fn can_get_and_get_mut() {
+        let mut list: SmallArrayList<i32, 4> = SmallArrayList::from_iter([1, 2, 3]);
+
+        assert_eq!(list.get(0), Some(&1));
+        assert_eq!(list.get(2), Some(&3));
+        assert_eq!(list.get(3), None);
+
+        *list.get_mut(0).unwrap() = 3;
+        list[2] = 1;
+        assert_eq!(list.get_mut(3), None);
+        assert_eq!(list.as_slice(), &[3, 2, 1]);
+    }
+
+    #[test]
+    fn can_insert() {
+        let mut list: SmallArrayList<i32, 4> = SmallArrayList::from_iter([1, 3]);
+
+        list.insert(0, 0);
+        list.insert(2, 2);
+        assert_eq!(list.as_slice(), &[0, 1, 2, 3]);
+        assert!(!list.spilled());
+
+        // Spills while inserting
+        list.insert(4, 4);
+        assert!(list.spilled());
+        assert_eq!(list.as_slice(), &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn can_remove() {
+        let mut list: SmallArrayList<i32, 8> = SmallArrayList::from_iter([1, 2, 3, 4, 5]);
+
+        assert_eq!(list.remove(0), 1); // Remove first
+        assert_eq!(list.remove(3), 5); // Remove last
+        assert_eq!(list.remove(1), 3); // Remove middle
+        assert_eq!(list.as_slice(), &[2, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds. Len: 3, Got: 3.")]
+    fn can_panic_when_removing_out_of_bounds() {
+        let mut list: SmallArrayList<i32, 4> = SmallArrayList::from_iter([1, 2, 3]);
+        list.remove(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds. Len: 3, Got: 4.")]
+    fn can_panic_when_inserting_out_of_bounds() {
+        let mut list: SmallArrayList<i32, 4> = SmallArrayList::from_iter([1, 2, 3]);
+        list.insert(4, 4);
+    }
+
+    #[test]
+    fn can_work_without_inline_capacity() {
+        let mut list: SmallArrayList<i32, 0> = SmallArrayList::new();
+        assert_eq!(list.capacity(), 0);
+
+        list.push(1);
+        assert!(list.spilled());
+        assert_eq!(list.as_slice(), &[1]);
+    }
+
+    #[test]
+    fn can_drop_every_element_exactly_once() {
+        let counter = Rc::new(Cell::new(0));
+
+        let mut list: SmallArrayList<DropCounter, 3> = SmallArrayList::new();
+        list.push(DropCounter::new(0, &counter));
+        list.push(DropCounter::new(0, &counter));
+        drop(list);
+        assert_eq!(counter.get(), 2);
+
+        let mut list: SmallArrayList<DropCounter, 3> = SmallArrayList::new();
+        for _ in 0..5 {
+            list.push(DropCounter::new(0, &counter));
+        }
+        drop(list.remove(1));
+        assert_eq!(counter.get(), 3);
+        drop(list);
+        assert_eq!(counter.get(), 7);
+    }
+
+    #[test]
+    fn can_split_off_and_append() {
+        let mut list: SmallArrayList<i32, 4> = SmallArrayList::from_iter([1, 2, 3, 4]);
+
+        let mut upper = list.split_off(2);
+        assert_eq!(list.as_slice(), &[1, 2]);
+        assert_eq!(upper.as_slice(), &[3, 4]);
+        assert!(!upper.spilled());
+
+        upper.as_mut_slice()[0] = 5;
+        list.append(&mut upper);
+        assert_eq!(list.as_slice(), &[1, 2, 5, 4]);
+        assert!(upper.is_empty());
+
+        assert!(list.split_off(4).is_empty());
+    }
+}
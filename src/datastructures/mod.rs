@@ -1,3 +1,4 @@
+pub(crate) mod arena;
 pub mod linked_lists;
 pub mod lists;
 pub mod maps;
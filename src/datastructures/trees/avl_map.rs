@@ -0,0 +1,417 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::mem;
+use std::ptr::NonNull;
+
+use super::avl_tree::AVLTree;
+
+/// An ordered map backed by an `AVLTree` whose entries are compared by their keys only
+pub struct AVLMap<K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    tree: AVLTree<KeyValue<K, V>>,
+}
+
+#[derive(Debug)]
+struct KeyValue<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K: PartialEq, V> PartialEq for KeyValue<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.key == other.key;
+    }
+}
+
+impl<K: PartialOrd, V> PartialOrd for KeyValue<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return self.key.partial_cmp(&other.key);
+    }
+}
+
+/// Public methods
+impl<K, V> AVLMap<K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    pub fn new() -> Self {
+        Self {
+            tree: AVLTree::new(),
+        }
+    }
+
+    /// Builds a map from the pairs, later values replacing earlier ones with the same key
+    pub fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        return map;
+    }
+
+    /// Returns the previous value if the key was already in the map. The key itself is
+    /// not updated in that case.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => return Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                return None;
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let entry = self.tree.get_by(|entry| key.partial_cmp(&entry.key))?;
+        return Some(&entry.value);
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let entry = self.tree.get_by_mut(|entry| key.partial_cmp(&entry.key))?;
+        return Some(&mut entry.value);
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        return self.get(key).is_some();
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.tree.remove_by(|entry| key.partial_cmp(&entry.key))?;
+        return Some(entry.value);
+    }
+
+    /// Gets the entry of the key for in-place manipulation
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        // The borrow checker can't tell that the map is only handed on when the lookup
+        // found nothing, so the found entry is kept as a pointer
+        let entry = self.tree.get_by_mut(|entry| key.partial_cmp(&entry.key));
+
+        match entry.map(NonNull::from) {
+            Some(entry) => Entry::Occupied(OccupiedEntry {
+                key,
+                entry,
+                map: self,
+            }),
+            None => Entry::Vacant(VacantEntry { key, map: self }),
+        }
+    }
+
+    /// Returns the entry with the smallest key
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        let entry = self.tree.min()?;
+        return Some((&entry.key, &entry.value));
+    }
+
+    /// Returns the entry with the largest key
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        let entry = self.tree.max()?;
+        return Some((&entry.key, &entry.value));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.tree.is_empty();
+    }
+
+    pub fn len(&self) -> usize {
+        return self.tree.len();
+    }
+}
+
+impl<K, V> Debug for AVLMap<K, V>
+where
+    K: PartialEq + PartialOrd + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries = self.tree.iter().map(|entry| (&entry.key, &entry.value));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl<K, V> Default for AVLMap<K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub enum Entry<'a, K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// Points right at the entry in the tree, which stays put for as long as the map is
+/// borrowed. The map is only touched again to remove the entry.
+pub struct OccupiedEntry<'a, K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    key: K,
+    entry: NonNull<KeyValue<K, V>>,
+    map: &'a mut AVLMap<K, V>,
+}
+
+pub struct VacantEntry<'a, K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    key: K,
+    map: &'a mut AVLMap<K, V>,
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        return self.or_insert_with(|| default);
+    }
+
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Calls `f` with the value if the entry is occupied
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        return self;
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: PartialEq + PartialOrd,
+    V: Default,
+{
+    pub fn or_default(self) -> &'a mut V {
+        return self.or_insert_with(V::default);
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    pub fn key(&self) -> &K {
+        return &self.key;
+    }
+
+    pub fn get(&self) -> &V {
+        unsafe { &(*self.entry.as_ptr()).value }
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut (*self.entry.as_ptr()).value }
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { &mut (*self.entry.as_ptr()).value }
+    }
+
+    /// Returns the replaced value
+    pub fn insert(&mut self, value: V) -> V {
+        return mem::replace(self.get_mut(), value);
+    }
+
+    pub fn remove(self) -> V {
+        return self.remove_entry().1;
+    }
+
+    /// Returns the key stored in the map along with the value
+    pub fn remove_entry(self) -> (K, V) {
+        let entry = self
+            .map
+            .tree
+            .remove_by(|entry| self.key.partial_cmp(&entry.key));
+        let entry = entry.expect("Occupied key should be in the map");
+        return (entry.key, entry.value);
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+where
+    K: PartialEq + PartialOrd,
+{
+    pub fn key(&self) -> &K {
+        return &self.key;
+    }
+
+    pub fn into_key(self) -> K {
+        return self.key;
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        let entry = KeyValue {
+            key: self.key,
+            value,
+        };
+        let entry = self.map.tree.insert_and_get_mut(entry);
+        return &mut entry.expect("Vacant key should not be in the map").value;
+    }
+}
+
+impl<K, V> Debug for Entry<'_, K, V>
+where
+    K: PartialEq + PartialOrd + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Entry::Occupied(entry) => f.debug_tuple("Occupied").field(entry.key()).finish(),
+            Entry::Vacant(entry) => f.debug_tuple("Vacant").field(entry.key()).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_insert_and_get() {
+        let mut map = AVLMap::new();
+        assert_eq!(map.insert(2, "two"), None);
+        assert_eq!(map.insert(1, "one"), None);
+        assert_eq!(map.insert(3, "three"), None);
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&1), Some(&"one"));
+        assert_eq!(map.get(&3), Some(&"three"));
+        assert_eq!(map.get(&4), None);
+        assert!(map.contains_key(&2));
+    }
+
+    #[test]
+    fn can_replace_values() {
+        let mut map = AVLMap::from_iter([(1, 10), (2, 20)]);
+        assert_eq!(map.insert(1, 11), Some(10));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&1), Some(&11));
+
+        *map.get_mut(&2).unwrap() += 1;
+        assert_eq!(map.get(&2), Some(&21));
+
+        let map = AVLMap::from_iter([(1, 'a'), (1, 'b'), (1, 'c')]);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&1), Some(&'c'));
+    }
+
+    #[test]
+    fn can_remove() {
+        let mut map = AVLMap::from_iter((0..10).map(|i| (i, i * i)));
+        assert_eq!(map.remove(&3), Some(9));
+        assert_eq!(map.remove(&3), None);
+        assert_eq!(map.remove(&42), None);
+        assert_eq!(map.len(), 9);
+
+        for i in (0..10).filter(|i| *i != 3) {
+            assert_eq!(map.remove(&i), Some(i * i));
+        }
+        assert!(map.is_empty());
+        assert_eq!(map.remove(&0), None);
+        assert_eq!(map.len(), 0);
+    }
+
+    #[test]
+    fn can_use_entries() {
+        let mut counts = AVLMap::new();
+        for word in ["b", "a", "b", "c", "b", "a"] {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+        assert_eq!(counts.get(&"a"), Some(&2));
+        assert_eq!(counts.get(&"b"), Some(&3));
+        assert_eq!(counts.get(&"c"), Some(&1));
+        assert_eq!(counts.len(), 3);
+
+        counts
+            .entry("a")
+            .and_modify(|count| *count *= 10)
+            .or_default();
+        counts
+            .entry("d")
+            .and_modify(|count| *count *= 10)
+            .or_default();
+        assert_eq!(counts.get(&"a"), Some(&20));
+        assert_eq!(counts.get(&"d"), Some(&0));
+
+        match counts.entry("c") {
+            Entry::Occupied(mut entry) => assert_eq!(entry.insert(5), 1),
+            Entry::Vacant(_) => panic!("Entry should be occupied"),
+        }
+        assert_eq!(format!("{:?}", counts.entry("e")), "Vacant(\"e\")");
+        assert_eq!(counts.get(&"c"), Some(&5));
+        assert_eq!(counts.len(), 4);
+    }
+
+    #[test]
+    fn can_remove_through_entries() {
+        let mut map = AVLMap::from_iter((0..10).map(|i| (i, i * i)));
+
+        match map.entry(3) {
+            Entry::Occupied(entry) => assert_eq!(entry.remove(), 9),
+            Entry::Vacant(_) => panic!("Entry should be occupied"),
+        }
+        assert_eq!(format!("{:?}", map.entry(3)), "Vacant(3)");
+        assert_eq!(map.len(), 9);
+
+        match map.entry(7) {
+            Entry::Occupied(mut entry) => {
+                *entry.get_mut() += 1;
+                assert_eq!(entry.get(), &50);
+                assert_eq!(entry.remove_entry(), (7, 50));
+            }
+            Entry::Vacant(_) => panic!("Entry should be occupied"),
+        }
+        assert_eq!(map.get(&7), None);
+        assert_eq!(map.len(), 8);
+
+        // The tree stays balanced and searchable after removing through entries
+        for i in [0, 1, 2, 4, 5, 6, 8, 9] {
+            assert_eq!(map.get(&i), Some(&(i * i)));
+        }
+    }
+
+    #[test]
+    fn can_get_first_and_last() {
+        let mut map = AVLMap::new();
+        assert_eq!(map.first_key_value(), None);
+        assert_eq!(map.last_key_value(), None);
+
+        for key in [5, 3, 8, 1, 9, 7] {
+            map.insert(key, key * 2);
+        }
+        assert_eq!(map.first_key_value(), Some((&1, &2)));
+        assert_eq!(map.last_key_value(), Some((&9, &18)));
+        assert_eq!(
+            format!("{:?}", map),
+            "{1: 2, 3: 6, 5: 10, 7: 14, 8: 16, 9: 18}"
+        );
+    }
+}
@@ -1,8 +1,13 @@
-use std::fmt::Debug;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
 use std::mem;
+use std::ops::{Bound, RangeBounds};
 use std::{marker::PhantomData, ptr::NonNull};
 
-#[derive(Debug)]
+use super::ordered_set::OrderedSet;
+
 pub struct AVLTree<T>
 where
     T: PartialEq + PartialOrd,
@@ -19,6 +24,8 @@ where
 {
     value: T,
     height: u64,
+    /// Number of nodes in the subtree rooted at this node
+    size: usize,
     left: Option<NonNull<Node<T>>>,
     right: Option<NonNull<Node<T>>>,
 }
@@ -31,6 +38,7 @@ where
         Self {
             value,
             height: 1,
+            size: 1,
             left: None,
             right: None,
         }
@@ -39,12 +47,15 @@ where
     fn new_as_ptr(value: T) -> NonNull<Self> {
         NonNull::from(Box::leak(Box::new(Self::new(value))))
     }
-
-    fn is_leaf(&self) -> bool {
-        return self.left.is_none() && self.right.is_none();
-    }
 }
 
+/// The nodes less than, equal to, and greater than the value a subtree was split at
+type SplitNodes<T> = (
+    Option<NonNull<Node<T>>>,
+    Option<NonNull<Node<T>>>,
+    Option<NonNull<Node<T>>>,
+);
+
 /// Public methods
 impl<T> AVLTree<T>
 where
@@ -58,51 +69,77 @@ where
         }
     }
 
+    /// Builds the tree by inserting the values one by one. Prefer `from_sorted_iter` if
+    /// the values are already sorted.
     pub fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = T>,
     {
         let mut tree = Self::new();
-        iter.into_iter().for_each(|value| tree.insert(value));
+        for value in iter {
+            tree.insert(value);
+        }
         return tree;
     }
 
-    pub fn insert(&mut self, value: T) {
-        if let Some(root) = self.root.take() {
-            unsafe {
-                self.root = Some(Self::insert_node(root, Node::new_as_ptr(value)));
-            };
-        } else {
-            self.root = Some(Node::new_as_ptr(value));
+    /// Builds a perfectly balanced tree from ascending values in O(n) without any rotations.
+    /// Repeated values are only kept once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are not sorted in ascending order.
+    pub fn from_sorted_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut values: Vec<T> = Vec::new();
+        for value in iter {
+            if let Some(last) = values.last() {
+                if value == *last {
+                    continue;
+                }
+                assert!(
+                    value > *last,
+                    "Values have to be sorted in ascending order."
+                );
+            }
+            values.push(value);
         }
 
-        self.len += 1;
+        let len = values.len();
+        let root = unsafe { Self::build_subtree(&mut values.into_iter(), len) };
+        Self {
+            root,
+            len,
+            mark: PhantomData,
+        }
+    }
+
+    /// Returns `false` and drops `value` if an equal value is already in the tree
+    pub fn insert(&mut self, value: T) -> bool {
+        return self.insert_and_get_mut(value).is_some();
     }
 
     pub fn remove(&mut self, value: &T) -> Option<T> {
-        if let Some(root) = self.root.take() {
-            let (new_root, value) = unsafe {
-                let (new_root, value) = Self::remove_node(root, value);
-                (new_root, value)
-            };
-            self.root = new_root;
-            self.len -= 1;
-            value
-        } else {
-            None
-        }
+        return self.remove_by(|node_value| value.partial_cmp(node_value));
     }
 
     pub fn contains(&self, value: &T) -> bool {
-        match self.root {
-            Some(root) => unsafe {
-                match Self::find_node(root, value) {
-                    Some(_) => true,
-                    None => false,
-                }
-            },
-            None => false,
-        }
+        return self
+            .get_by(|node_value| value.partial_cmp(node_value))
+            .is_some();
+    }
+
+    /// Returns the smallest value in the tree
+    pub fn min(&self) -> Option<&T> {
+        let root = self.root?;
+        unsafe { Some(&(*Self::min_node(root).as_ptr()).value) }
+    }
+
+    /// Returns the largest value in the tree
+    pub fn max(&self) -> Option<&T> {
+        let root = self.root?;
+        unsafe { Some(&(*Self::max_node(root).as_ptr()).value) }
     }
 
     pub fn height(&self) -> u64 {
@@ -171,21 +208,320 @@ where
         return vec;
     }
 
-    /// Returns an iterator over references to the underlying values *inorder*
-    pub fn iter(&self) -> Iter<T> {
-        let current = if let Some(root) = self.root {
-            Some(root)
-        } else {
-            None
+    /// Returns the largest value less than or equal to `value`
+    pub fn floor(&self, value: &T) -> Option<&T> {
+        return self.last_where(|node_value| node_value <= value);
+    }
+
+    /// Returns the smallest value greater than or equal to `value`
+    pub fn ceiling(&self, value: &T) -> Option<&T> {
+        return self.first_where(|node_value| node_value >= value);
+    }
+
+    /// Returns the largest value strictly less than `value`
+    pub fn predecessor(&self, value: &T) -> Option<&T> {
+        return self.last_where(|node_value| node_value < value);
+    }
+
+    /// Returns the smallest value strictly greater than `value`
+    pub fn successor(&self, value: &T) -> Option<&T> {
+        return self.first_where(|node_value| node_value > value);
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        let root = self.root?;
+        let (new_root, node) = unsafe { Self::remove_min_node(root) };
+        self.root = new_root;
+        self.len -= 1;
+
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+        return Some(node.value);
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        let root = self.root?;
+        let (new_root, node) = unsafe { Self::remove_max_node(root) };
+        self.root = new_root;
+        self.len -= 1;
+
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+        return Some(node.value);
+    }
+
+    /// Returns the `k`-th smallest value, counting from 0, in O(log n)
+    pub fn select(&self, k: usize) -> Option<&T> {
+        let mut k = k;
+        let mut curr_node = self.root;
+
+        while let Some(node) = curr_node {
+            unsafe {
+                let left_size = Self::size_of((*node.as_ptr()).left);
+                if k < left_size {
+                    curr_node = (*node.as_ptr()).left;
+                } else if k == left_size {
+                    return Some(&(*node.as_ptr()).value);
+                } else {
+                    k -= left_size + 1;
+                    curr_node = (*node.as_ptr()).right;
+                }
+            }
+        }
+
+        return None;
+    }
+
+    /// Returns the number of values strictly less than `value` in O(log n), which is also
+    /// the index `value` has or would have in sorted order
+    pub fn rank(&self, value: &T) -> usize {
+        return self.count_where(|node_value| node_value < value);
+    }
+
+    /// Returns the number of values within `range` in O(log n)
+    pub fn count_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<T>,
+    {
+        let below_start = match range.start_bound() {
+            Bound::Included(start) => self.count_where(|value| value < start),
+            Bound::Excluded(start) => self.count_where(|value| value <= start),
+            Bound::Unbounded => 0,
+        };
+
+        let up_to_end = match range.end_bound() {
+            Bound::Included(end) => self.count_where(|value| value <= end),
+            Bound::Excluded(end) => self.count_where(|value| value < end),
+            Bound::Unbounded => self.len,
         };
+
+        // The ends of an empty range may have passed each other
+        return up_to_end.saturating_sub(below_start);
+    }
+
+    /// Returns an iterator over references to the underlying values *inorder*
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
-            stack: Vec::with_capacity(self.len()),
-            current,
+            range: self.range(..),
             len: self.len(),
             visited: 0,
+        }
+    }
+
+    /// Returns an iterator over mutable references to the underlying values *inorder*.
+    /// Changing the values in a way that changes their order is a logic error, after
+    /// which the tree won't find them anymore.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            nodes: Inorder::new(self.root),
+            len: self.len(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over references to the underlying values *preorder*
+    pub fn preorder_iter(&self) -> PreorderIter<'_, T> {
+        PreorderIter {
+            stack: self.root.into_iter().collect(),
+            len: self.len(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over references to the underlying values *postorder*
+    pub fn postorder_iter(&self) -> PostorderIter<'_, T> {
+        PostorderIter {
+            stack: self.root.map(|root| (root, false)).into_iter().collect(),
+            len: self.len(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over references to the underlying values level by level, from
+    /// left to right
+    pub fn level_order_iter(&self) -> LevelOrderIter<'_, T> {
+        LevelOrderIter {
+            queue: self.root.into_iter().collect(),
+            len: self.len(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the values within `range` *inorder*. Both ends of the range
+    /// are found in O(log n), and the iterator can be consumed from either end.
+    pub fn range<R>(&self, range: R) -> Range<'_, T>
+    where
+        R: RangeBounds<T>,
+    {
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        if let Some(root) = self.root {
+            unsafe {
+                Self::push_front_path(root, &mut front, |value| match range.start_bound() {
+                    Bound::Included(start) => value >= start,
+                    Bound::Excluded(start) => value > start,
+                    Bound::Unbounded => true,
+                });
+                Self::push_back_path(root, &mut back, |value| match range.end_bound() {
+                    Bound::Included(end) => value <= end,
+                    Bound::Excluded(end) => value < end,
+                    Bound::Unbounded => true,
+                });
+            }
+        }
+
+        // The range is empty if its ends passed each other
+        let is_empty = match (front.last(), back.last()) {
+            (Some(first), Some(last)) => unsafe {
+                (*first.as_ptr()).value > (*last.as_ptr()).value
+            },
+            _ => true,
+        };
+        if is_empty {
+            front.clear();
+            back.clear();
+        }
+
+        Range {
+            nodes: Inorder { front, back },
             marker: PhantomData,
         }
     }
+
+    /// Checks every invariant of the tree in O(n) and returns the first violation found
+    /// walking the tree in *preorder*
+    pub fn validate(&self) -> Result<(), InvariantViolation<'_, T>> {
+        /// Returns the actual height and size of the subtree
+        unsafe fn validate_recur<'a, T>(
+            node: NonNull<Node<T>>,
+            lower: Option<&'a T>,
+            upper: Option<&'a T>,
+        ) -> Result<(u64, usize), InvariantViolation<'a, T>>
+        where
+            T: PartialEq + PartialOrd,
+        {
+            let node = &*node.as_ptr();
+
+            // Every value has to lie strictly between its closest ancestors on either side
+            if let Some(lower) = lower {
+                if node.value.partial_cmp(lower) != Some(Ordering::Greater) {
+                    return Err(InvariantViolation::Ordering {
+                        value: &node.value,
+                        ancestor: lower,
+                    });
+                }
+            }
+            if let Some(upper) = upper {
+                if node.value.partial_cmp(upper) != Some(Ordering::Less) {
+                    return Err(InvariantViolation::Ordering {
+                        value: &node.value,
+                        ancestor: upper,
+                    });
+                }
+            }
+
+            let (left_height, left_size) = match node.left {
+                Some(left) => validate_recur(left, lower, Some(&node.value))?,
+                None => (0, 0),
+            };
+            let (right_height, right_size) = match node.right {
+                Some(right) => validate_recur(right, Some(&node.value), upper)?,
+                None => (0, 0),
+            };
+
+            let height = left_height.max(right_height) + 1;
+            if node.height != height {
+                return Err(InvariantViolation::Height {
+                    value: &node.value,
+                    cached: node.height,
+                    actual: height,
+                });
+            }
+
+            let balance = left_height as i64 - right_height as i64;
+            if balance.abs() > 1 {
+                return Err(InvariantViolation::Balance {
+                    value: &node.value,
+                    balance,
+                });
+            }
+
+            let size = left_size + right_size + 1;
+            if node.size != size {
+                return Err(InvariantViolation::Size {
+                    value: &node.value,
+                    cached: node.size,
+                    actual: size,
+                });
+            }
+
+            return Ok((height, size));
+        }
+
+        let size = match self.root {
+            Some(root) => unsafe { validate_recur(root, None, None)?.1 },
+            None => 0,
+        };
+
+        if self.len != size {
+            return Err(InvariantViolation::Len {
+                len: self.len,
+                actual: size,
+            });
+        }
+        return Ok(());
+    }
+}
+
+// Set algebra built on splitting trees at a value and joining them back together, which
+// take O(log n) each. The set operations take O(m log(n / m + 1)) for trees of sizes m <= n.
+impl<T> AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    /// Returns the values that are in either tree. Values that are in both trees are
+    /// taken from `self`.
+    pub fn union(mut self, mut other: Self) -> Self {
+        let root = unsafe { Self::union_nodes(self.root.take(), other.root.take()) };
+        return Self::from_root(root);
+    }
+
+    /// Returns the values that are in both trees, taken from `self`
+    pub fn intersection(mut self, mut other: Self) -> Self {
+        let root = unsafe { Self::intersect_nodes(self.root.take(), other.root.take()) };
+        return Self::from_root(root);
+    }
+
+    /// Returns the values of `self` that are not in `other`
+    pub fn difference(mut self, mut other: Self) -> Self {
+        let root = unsafe { Self::subtract_nodes(self.root.take(), other.root.take()) };
+        return Self::from_root(root);
+    }
+
+    /// Splits the tree into the values less than `value`, the value equal to it if there is
+    /// one, and the values greater than it
+    pub fn split(mut self, value: &T) -> (Self, Option<T>, Self) {
+        let (less, equal, greater) = unsafe { Self::split_node(self.root.take(), value) };
+        let equal = equal.map(|node| unsafe { Box::from_raw(node.as_ptr()).value });
+
+        return (Self::from_root(less), equal, Self::from_root(greater));
+    }
+
+    /// Concatenates two trees in O(log n)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values of `self` are not all less than the values of `other`.
+    pub fn join(mut self, mut other: Self) -> Self {
+        if let (Some(max), Some(min)) = (self.max(), other.min()) {
+            assert!(
+                max < min,
+                "All values of the left tree have to be less than those of the right tree."
+            );
+        }
+
+        let root = unsafe { Self::join_two_nodes(self.root.take(), other.root.take()) };
+        return Self::from_root(root);
+    }
 }
 
 impl<T> AVLTree<T>
@@ -233,6 +569,229 @@ where
             println!("Tree is empty");
         }
     }
+
+    /// Returns the tree in the Graphviz DOT format, labelling every node with its value,
+    /// height and balance factor. Missing children are drawn as points so that left and
+    /// right children can be told apart.
+    pub fn to_dot(&self) -> String {
+        /// Returns the id of the node in the graph
+        unsafe fn to_dot_recur<T>(
+            node: NonNull<Node<T>>,
+            dot: &mut String,
+            next_id: &mut usize,
+        ) -> usize
+        where
+            T: PartialOrd + PartialEq + Debug,
+        {
+            let id = *next_id;
+            *next_id += 1;
+
+            let label = format!("{:?}", (*node.as_ptr()).value)
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"");
+            dot.push_str(&format!(
+                "    node{} [label=\"{}\\nh: {}, b: {}\"];\n",
+                id,
+                label,
+                (*node.as_ptr()).height,
+                AVLTree::get_balance_factor(node)
+            ));
+
+            let (left, right) = ((*node.as_ptr()).left, (*node.as_ptr()).right);
+            if left.is_none() && right.is_none() {
+                return id;
+            }
+
+            for child in [left, right] {
+                match child {
+                    Some(child) => {
+                        let child_id = to_dot_recur(child, dot, next_id);
+                        dot.push_str(&format!("    node{} -> node{};\n", id, child_id));
+                    }
+                    None => {
+                        dot.push_str(&format!("    null{} [shape=point];\n", *next_id));
+                        dot.push_str(&format!("    node{} -> null{};\n", id, *next_id));
+                        *next_id += 1;
+                    }
+                }
+            }
+
+            return id;
+        }
+
+        let mut dot = String::from("digraph AVLTree {\n");
+        if let Some(root) = self.root {
+            unsafe { to_dot_recur(root, &mut dot, &mut 0) };
+        }
+        dot.push_str("}\n");
+        return dot;
+    }
+
+    /// Renders the tree sideways with box-drawing lines, one node per line, marking every
+    /// child as the left (`L`) or right (`R`) one
+    pub fn to_ascii(&self) -> String {
+        unsafe fn to_ascii_recur<T>(node: NonNull<Node<T>>, prefix: &str, ascii: &mut String)
+        where
+            T: PartialOrd + PartialEq + Debug,
+        {
+            let children = [("L", (*node.as_ptr()).left), ("R", (*node.as_ptr()).right)];
+            let children: Vec<_> = children
+                .into_iter()
+                .filter_map(|(side, child)| Some((side, child?)))
+                .collect();
+
+            for (i, (side, child)) in children.iter().enumerate() {
+                let is_last = i == children.len() - 1;
+                let (branch, indent) = if is_last {
+                    ("└── ", "    ")
+                } else {
+                    ("├── ", "│   ")
+                };
+
+                ascii.push_str(&format!(
+                    "{}{}{}: {:?}\n",
+                    prefix,
+                    branch,
+                    side,
+                    (*child.as_ptr()).value
+                ));
+                to_ascii_recur(*child, &format!("{}{}", prefix, indent), ascii);
+            }
+        }
+
+        let mut ascii = String::new();
+        if let Some(root) = self.root {
+            unsafe {
+                ascii.push_str(&format!("{:?}\n", (*root.as_ptr()).value));
+                to_ascii_recur(root, "", &mut ascii);
+            }
+        }
+        return ascii;
+    }
+}
+
+/// The first broken invariant found by `AVLTree::validate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation<'a, T> {
+    /// `value` is on the wrong side of one of its ancestors
+    Ordering { value: &'a T, ancestor: &'a T },
+    /// The cached height of the node with `value` doesn't match the subtree
+    Height {
+        value: &'a T,
+        cached: u64,
+        actual: u64,
+    },
+    /// The heights of the subtrees of the node with `value` differ by more than one
+    Balance { value: &'a T, balance: i64 },
+    /// The cached size of the node with `value` doesn't match the subtree
+    Size {
+        value: &'a T,
+        cached: usize,
+        actual: usize,
+    },
+    /// The tree's `len` doesn't match the number of nodes
+    Len { len: usize, actual: usize },
+}
+
+impl<T: Debug> fmt::Display for InvariantViolation<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantViolation::Ordering { value, ancestor } => {
+                write!(
+                    f,
+                    "{:?} is on the wrong side of its ancestor {:?}",
+                    value, ancestor
+                )
+            }
+            InvariantViolation::Height {
+                value,
+                cached,
+                actual,
+            } => write!(
+                f,
+                "{:?} has a cached height of {} instead of {}",
+                value, cached, actual
+            ),
+            InvariantViolation::Balance { value, balance } => {
+                write!(f, "{:?} has a balance factor of {}", value, balance)
+            }
+            InvariantViolation::Size {
+                value,
+                cached,
+                actual,
+            } => write!(
+                f,
+                "{:?} has a cached size of {} instead of {}",
+                value, cached, actual
+            ),
+            InvariantViolation::Len { len, actual } => {
+                write!(f, "the tree has a len of {} but {} nodes", len, actual)
+            }
+        }
+    }
+}
+
+impl<T: Debug> std::error::Error for InvariantViolation<'_, T> {}
+
+// Lookups driven by a comparison against the values in the tree, so that `AVLMap` can
+// search its entries by key. `compare` returns the ordering of the searched value relative
+// to the given value in the tree, `None` meaning that they can't be compared.
+impl<T> AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    pub(super) fn get_by<F>(&self, compare: F) -> Option<&T>
+    where
+        F: Fn(&T) -> Option<Ordering>,
+    {
+        let node = unsafe { Self::find_node(self.root?, &compare)? };
+        unsafe { Some(&(*node.as_ptr()).value) }
+    }
+
+    pub(super) fn get_by_mut<F>(&mut self, compare: F) -> Option<&mut T>
+    where
+        F: Fn(&T) -> Option<Ordering>,
+    {
+        let node = unsafe { Self::find_node(self.root?, &compare)? };
+        unsafe { Some(&mut (*node.as_ptr()).value) }
+    }
+
+    pub(super) fn remove_by<F>(&mut self, compare: F) -> Option<T>
+    where
+        F: Fn(&T) -> Option<Ordering>,
+    {
+        let root = self.root?;
+        let (new_root, value) = unsafe { Self::remove_node(root, &compare) };
+        self.root = new_root;
+
+        if value.is_some() {
+            self.len -= 1;
+        }
+        return value;
+    }
+
+    /// Returns a reference to the inserted value, or `None` if an equal value is already in
+    /// the tree
+    pub(super) fn insert_and_get_mut(&mut self, value: T) -> Option<&mut T> {
+        let node = Node::new_as_ptr(value);
+
+        match self.root {
+            Some(root) => unsafe {
+                match Self::insert_node(root, node) {
+                    Some(new_root) => self.root = Some(new_root),
+                    None => {
+                        let _ = Box::from_raw(node.as_ptr());
+                        return None;
+                    }
+                }
+            },
+            None => self.root = Some(node),
+        }
+
+        self.len += 1;
+        // Rotations only relink the nodes, so the new node is still where it was allocated
+        unsafe { Some(&mut (*node.as_ptr()).value) }
+    }
 }
 
 // Helper functions
@@ -240,141 +799,506 @@ impl<T> AVLTree<T>
 where
     T: PartialEq + PartialOrd,
 {
-    unsafe fn insert_node(root: NonNull<Node<T>>, node: NonNull<Node<T>>) -> NonNull<Node<T>> {
+    /// Returns the new root of the subtree, or `None` if an equal value is already in the
+    /// subtree, in which case it is left untouched
+    unsafe fn insert_node(
+        root: NonNull<Node<T>>,
+        node: NonNull<Node<T>>,
+    ) -> Option<NonNull<Node<T>>> {
         if (*node.as_ptr()).value < (*root.as_ptr()).value {
             match (*root.as_ptr()).left {
                 Some(left) => {
-                    (*root.as_ptr()).left = Some(Self::insert_node(left, node));
+                    (*root.as_ptr()).left = Some(Self::insert_node(left, node)?);
                 }
                 None => (*root.as_ptr()).left = Some(node),
             }
         } else if (*node.as_ptr()).value > (*root.as_ptr()).value {
             match (*root.as_ptr()).right {
                 Some(right) => {
-                    (*root.as_ptr()).right = Some(Self::insert_node(right, node));
+                    (*root.as_ptr()).right = Some(Self::insert_node(right, node)?);
                 }
                 None => (*root.as_ptr()).right = Some(node),
             }
         } else {
-            return node;
+            return None;
         }
 
         Self::update_height(root);
-        return Self::apply_rotation(root);
+        Self::update_size(root);
+        return Some(Self::apply_rotation(root));
     }
+
     /// Returns the new `node` in place of the old `node` and the value of the old node in a tuple
-    unsafe fn remove_node(
+    unsafe fn remove_node<F>(
         root: NonNull<Node<T>>,
-        value: &T,
+        compare: &F,
     ) -> (Option<NonNull<Node<T>>>, Option<T>)
     where
-        T: PartialEq + PartialOrd,
+        F: Fn(&T) -> Option<Ordering>,
     {
         let mut ret_value = None;
-        if value < &(*root.as_ptr()).value {
-            if let Some(left) = (*root.as_ptr()).left {
-                let (new_root, value) = Self::remove_node(left, value);
-                ret_value = value;
-                (*root.as_ptr()).left = new_root;
-            }
-        } else if value > &(*root.as_ptr()).value {
-            if let Some(right) = (*root.as_ptr()).right {
-                let (new_root, value) = Self::remove_node(right, value);
-                ret_value = value;
-                (*root.as_ptr()).right = new_root;
+        match compare(&(*root.as_ptr()).value) {
+            Some(Ordering::Less) => {
+                if let Some(left) = (*root.as_ptr()).left {
+                    let (new_root, value) = Self::remove_node(left, compare);
+                    ret_value = value;
+                    (*root.as_ptr()).left = new_root;
+                }
             }
-        } else {
-            // One child or leaf node
-            if (*root.as_ptr()).left.is_none() {
-                let root = Box::from_raw(root.as_ptr());
-                return (root.right, Some(root.value));
-            } else if (*root.as_ptr()).right.is_none() {
-                let root = Box::from_raw(root.as_ptr());
-                return (root.left, Some(root.value));
-            }
-
-            // Two children
-            let mut succ_parent = root;
-            let mut succ = (*root.as_ptr()).right;
-
-            while let Some(succ_node) = succ {
-                if let Some(left) = (*succ_node.as_ptr()).left {
-                    succ_parent = succ_node;
-                    succ = Some(left);
-                } else {
-                    break;
+            Some(Ordering::Greater) => {
+                if let Some(right) = (*root.as_ptr()).right {
+                    let (new_root, value) = Self::remove_node(right, compare);
+                    ret_value = value;
+                    (*root.as_ptr()).right = new_root;
                 }
             }
+            None => return (Some(root), None),
+            Some(Ordering::Equal) => {
+                // One child or leaf node
+                if (*root.as_ptr()).left.is_none() {
+                    let root = Box::from_raw(root.as_ptr());
+                    return (root.right, Some(root.value));
+                } else if (*root.as_ptr()).right.is_none() {
+                    let root = Box::from_raw(root.as_ptr());
+                    return (root.left, Some(root.value));
+                }
 
-            if let Some(succ_node) = succ {
-                let succ_node = Box::from_raw(succ_node.as_ptr());
-                (*succ_parent.as_ptr()).left = None;
-                ret_value = Some(mem::replace(&mut (*root.as_ptr()).value, succ_node.value));
-            } else {
-                let root = Box::from_raw(root.as_ptr());
-                return (None, Some(root.value));
+                // Two children, take over the value of the inorder successor, which is
+                // detached from the right subtree and rebalances it on the way back up
+                let right = (*root.as_ptr()).right.expect("Right node should exist");
+                let (new_right, succ) = Self::remove_min_node(right);
+                (*root.as_ptr()).right = new_right;
+
+                let succ = Box::from_raw(succ.as_ptr());
+                ret_value = Some(mem::replace(&mut (*root.as_ptr()).value, succ.value));
             }
         }
 
-        println!("1");
         Self::update_height(root);
-        println!("2");
+        Self::update_size(root);
         let new_root = Self::apply_rotation(root);
         (Some(new_root), ret_value)
     }
 
-    /// Remove the node with the given `value` in the subtree of the given `root` node
-    unsafe fn find_node(root: NonNull<Node<T>>, value: &T) -> Option<NonNull<Node<T>>> {
-        let root_value = &(*root.as_ptr()).value;
-
-        if value < root_value {
-            if let Some(left) = (*root.as_ptr()).left {
-                Self::find_node(left, value)
-            } else {
-                None
-            }
-        } else if value > root_value {
-            if let Some(right) = (*root.as_ptr()).right {
-                Self::find_node(right, value)
-            } else {
-                None
-            }
-        } else if value == root_value {
-            Some(root)
-        } else {
-            None
+    /// Finds the node that `compare` matches in the subtree of the given `root` node
+    unsafe fn find_node<F>(root: NonNull<Node<T>>, compare: &F) -> Option<NonNull<Node<T>>>
+    where
+        F: Fn(&T) -> Option<Ordering>,
+    {
+        match compare(&(*root.as_ptr()).value)? {
+            Ordering::Less => Self::find_node((*root.as_ptr()).left?, compare),
+            Ordering::Greater => Self::find_node((*root.as_ptr()).right?, compare),
+            Ordering::Equal => Some(root),
         }
     }
 
-    fn update_height(node: NonNull<Node<T>>) {
-        fn update_height_recur<T>(node: NonNull<Node<T>>) -> u64
-        where
-            T: PartialOrd + PartialEq,
-        {
-            let node = unsafe { node.as_ref() };
-
-            if node.is_leaf() {
-                return 1;
-            }
-
-            let left_height = if let Some(left) = node.left {
-                update_height_recur(left)
-            } else {
-                0
-            };
-
-            let right_height = if let Some(right) = node.right {
-                update_height_recur(right)
-            } else {
-                0
-            };
-
-            left_height.max(right_height) + 1
+    unsafe fn min_node(root: NonNull<Node<T>>) -> NonNull<Node<T>> {
+        let mut node = root;
+        while let Some(left) = (*node.as_ptr()).left {
+            node = left;
         }
+        return node;
+    }
 
-        unsafe {
-            (*node.as_ptr()).height = update_height_recur(node);
+    unsafe fn max_node(root: NonNull<Node<T>>) -> NonNull<Node<T>> {
+        let mut node = root;
+        while let Some(right) = (*node.as_ptr()).right {
+            node = right;
         }
+        return node;
+    }
+
+    /// Wraps a subtree into a tree, taking the length from the cached size of the root
+    fn from_root(root: Option<NonNull<Node<T>>>) -> Self {
+        Self {
+            root,
+            len: Self::size_of(root),
+            mark: PhantomData,
+        }
+    }
+
+    unsafe fn drop_subtree(node: NonNull<Node<T>>) {
+        if let Some(left) = (*node.as_ptr()).left {
+            Self::drop_subtree(left);
+        }
+
+        if let Some(right) = (*node.as_ptr()).right {
+            Self::drop_subtree(right);
+        }
+
+        let _ = Box::from_raw(node.as_ptr());
+    }
+
+    unsafe fn clone_subtree(node: NonNull<Node<T>>) -> NonNull<Node<T>>
+    where
+        T: Clone,
+    {
+        let clone = Node::new_as_ptr((*node.as_ptr()).value.clone());
+        (*clone.as_ptr()).height = (*node.as_ptr()).height;
+        (*clone.as_ptr()).size = (*node.as_ptr()).size;
+        (*clone.as_ptr()).left = (*node.as_ptr()).left.map(|left| Self::clone_subtree(left));
+        (*clone.as_ptr()).right = (*node.as_ptr())
+            .right
+            .map(|right| Self::clone_subtree(right));
+
+        return clone;
+    }
+
+    /// Builds a perfectly balanced subtree out of the next `len` values of `values`
+    unsafe fn build_subtree<I>(values: &mut I, len: usize) -> Option<NonNull<Node<T>>>
+    where
+        I: Iterator<Item = T>,
+    {
+        if len == 0 {
+            return None;
+        }
+
+        let left = Self::build_subtree(values, len / 2);
+        let node = Node::new_as_ptr(values.next().expect("Values should not run out"));
+        let right = Self::build_subtree(values, len - len / 2 - 1);
+
+        (*node.as_ptr()).left = left;
+        (*node.as_ptr()).right = right;
+        Self::update_height(node);
+        Self::update_size(node);
+
+        return Some(node);
+    }
+
+    /// Joins the subtrees with `mid` in between, where all values of `left` are less than
+    /// the value of `mid` and all values of `right` are greater. `mid` is attached where the
+    /// heights of both sides meet and the path above it is rebalanced, which takes time
+    /// proportional to the difference of their heights.
+    unsafe fn join_nodes(
+        left: Option<NonNull<Node<T>>>,
+        mid: NonNull<Node<T>>,
+        right: Option<NonNull<Node<T>>>,
+    ) -> NonNull<Node<T>> {
+        let left_height = Self::height_of(left);
+        let right_height = Self::height_of(right);
+
+        if left_height > right_height + 1 {
+            let left = left.expect("Left node should exist");
+            (*left.as_ptr()).right = Some(Self::join_nodes((*left.as_ptr()).right, mid, right));
+
+            Self::update_height(left);
+            Self::update_size(left);
+            return Self::apply_rotation(left);
+        }
+
+        if right_height > left_height + 1 {
+            let right = right.expect("Right node should exist");
+            (*right.as_ptr()).left = Some(Self::join_nodes(left, mid, (*right.as_ptr()).left));
+
+            Self::update_height(right);
+            Self::update_size(right);
+            return Self::apply_rotation(right);
+        }
+
+        (*mid.as_ptr()).left = left;
+        (*mid.as_ptr()).right = right;
+        Self::update_height(mid);
+        Self::update_size(mid);
+        return mid;
+    }
+
+    /// Joins the subtrees where all values of `left` are less than the values of `right`
+    unsafe fn join_two_nodes(
+        left: Option<NonNull<Node<T>>>,
+        right: Option<NonNull<Node<T>>>,
+    ) -> Option<NonNull<Node<T>>> {
+        match left {
+            Some(left) => {
+                let (left, max) = Self::remove_max_node(left);
+                return Some(Self::join_nodes(left, max, right));
+            }
+            None => return right,
+        }
+    }
+
+    /// Splits the subtree into the nodes less than `value`, the detached node equal to it,
+    /// and the nodes greater than it
+    unsafe fn split_node(root: Option<NonNull<Node<T>>>, value: &T) -> SplitNodes<T> {
+        let Some(root) = root else {
+            return (None, None, None);
+        };
+
+        let left = (*root.as_ptr()).left.take();
+        let right = (*root.as_ptr()).right.take();
+
+        if value < &(*root.as_ptr()).value {
+            let (less, equal, greater) = Self::split_node(left, value);
+            return (less, equal, Some(Self::join_nodes(greater, root, right)));
+        } else if value > &(*root.as_ptr()).value {
+            let (less, equal, greater) = Self::split_node(right, value);
+            return (Some(Self::join_nodes(left, root, less)), equal, greater);
+        }
+
+        Self::update_height(root);
+        Self::update_size(root);
+        return (left, Some(root), right);
+    }
+
+    unsafe fn union_nodes(
+        a: Option<NonNull<Node<T>>>,
+        b: Option<NonNull<Node<T>>>,
+    ) -> Option<NonNull<Node<T>>> {
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            (a, None) => return a,
+            (None, b) => return b,
+        };
+
+        let a_left = (*a.as_ptr()).left.take();
+        let a_right = (*a.as_ptr()).right.take();
+        let (b_less, b_equal, b_greater) = Self::split_node(Some(b), &(*a.as_ptr()).value);
+        if let Some(b_equal) = b_equal {
+            Self::drop_subtree(b_equal);
+        }
+
+        let left = Self::union_nodes(a_left, b_less);
+        let right = Self::union_nodes(a_right, b_greater);
+        return Some(Self::join_nodes(left, a, right));
+    }
+
+    unsafe fn intersect_nodes(
+        a: Option<NonNull<Node<T>>>,
+        b: Option<NonNull<Node<T>>>,
+    ) -> Option<NonNull<Node<T>>> {
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            (a, b) => {
+                for node in [a, b].into_iter().flatten() {
+                    Self::drop_subtree(node);
+                }
+                return None;
+            }
+        };
+
+        let a_left = (*a.as_ptr()).left.take();
+        let a_right = (*a.as_ptr()).right.take();
+        let (b_less, b_equal, b_greater) = Self::split_node(Some(b), &(*a.as_ptr()).value);
+
+        let left = Self::intersect_nodes(a_left, b_less);
+        let right = Self::intersect_nodes(a_right, b_greater);
+        match b_equal {
+            Some(b_equal) => {
+                Self::drop_subtree(b_equal);
+                return Some(Self::join_nodes(left, a, right));
+            }
+            None => {
+                Self::drop_subtree(a);
+                return Self::join_two_nodes(left, right);
+            }
+        }
+    }
+
+    unsafe fn subtract_nodes(
+        a: Option<NonNull<Node<T>>>,
+        b: Option<NonNull<Node<T>>>,
+    ) -> Option<NonNull<Node<T>>> {
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            (a, None) => return a,
+            (None, Some(b)) => {
+                Self::drop_subtree(b);
+                return None;
+            }
+        };
+
+        let b_left = (*b.as_ptr()).left.take();
+        let b_right = (*b.as_ptr()).right.take();
+        let (a_less, a_equal, a_greater) = Self::split_node(Some(a), &(*b.as_ptr()).value);
+        Self::drop_subtree(b);
+        if let Some(a_equal) = a_equal {
+            Self::drop_subtree(a_equal);
+        }
+
+        let left = Self::subtract_nodes(a_less, b_left);
+        let right = Self::subtract_nodes(a_greater, b_right);
+        return Self::join_two_nodes(left, right);
+    }
+
+    /// Detaches the smallest node of the subtree, returning the new root of the subtree
+    /// and the detached node
+    unsafe fn remove_min_node(
+        root: NonNull<Node<T>>,
+    ) -> (Option<NonNull<Node<T>>>, NonNull<Node<T>>) {
+        match (*root.as_ptr()).left {
+            Some(left) => {
+                let (new_left, min) = Self::remove_min_node(left);
+                (*root.as_ptr()).left = new_left;
+
+                Self::update_height(root);
+                Self::update_size(root);
+                return (Some(Self::apply_rotation(root)), min);
+            }
+            None => {
+                let right = (*root.as_ptr()).right.take();
+                return (right, root);
+            }
+        }
+    }
+
+    /// Detaches the largest node of the subtree, returning the new root of the subtree
+    /// and the detached node
+    unsafe fn remove_max_node(
+        root: NonNull<Node<T>>,
+    ) -> (Option<NonNull<Node<T>>>, NonNull<Node<T>>) {
+        match (*root.as_ptr()).right {
+            Some(right) => {
+                let (new_right, max) = Self::remove_max_node(right);
+                (*root.as_ptr()).right = new_right;
+
+                Self::update_height(root);
+                Self::update_size(root);
+                return (Some(Self::apply_rotation(root)), max);
+            }
+            None => {
+                let left = (*root.as_ptr()).left.take();
+                return (left, root);
+            }
+        }
+    }
+
+    /// Returns the largest value that `pred` holds for, given that it holds for all values
+    /// up to some point and for none after it
+    fn last_where<F>(&self, pred: F) -> Option<&T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut found = None;
+        let mut curr_node = self.root;
+
+        while let Some(node) = curr_node {
+            unsafe {
+                if pred(&(*node.as_ptr()).value) {
+                    found = Some(&(*node.as_ptr()).value);
+                    curr_node = (*node.as_ptr()).right;
+                } else {
+                    curr_node = (*node.as_ptr()).left;
+                }
+            }
+        }
+
+        return found;
+    }
+
+    /// Returns the smallest value that `pred` holds for, given that it holds for all values
+    /// from some point on and for none before it
+    fn first_where<F>(&self, pred: F) -> Option<&T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut found = None;
+        let mut curr_node = self.root;
+
+        while let Some(node) = curr_node {
+            unsafe {
+                if pred(&(*node.as_ptr()).value) {
+                    found = Some(&(*node.as_ptr()).value);
+                    curr_node = (*node.as_ptr()).left;
+                } else {
+                    curr_node = (*node.as_ptr()).right;
+                }
+            }
+        }
+
+        return found;
+    }
+
+    /// Pushes the path to the smallest value of the subtree that `in_range` holds for onto
+    /// `stack`, leaving out the nodes that are smaller than it
+    unsafe fn push_front_path<F>(
+        root: NonNull<Node<T>>,
+        stack: &mut Vec<NonNull<Node<T>>>,
+        in_range: F,
+    ) where
+        F: Fn(&T) -> bool,
+    {
+        let mut curr_node = Some(root);
+        while let Some(node) = curr_node {
+            if in_range(&(*node.as_ptr()).value) {
+                stack.push(node);
+                curr_node = (*node.as_ptr()).left;
+            } else {
+                curr_node = (*node.as_ptr()).right;
+            }
+        }
+    }
+
+    /// Pushes the path to the largest value of the subtree that `in_range` holds for onto
+    /// `stack`, leaving out the nodes that are larger than it
+    unsafe fn push_back_path<F>(
+        root: NonNull<Node<T>>,
+        stack: &mut Vec<NonNull<Node<T>>>,
+        in_range: F,
+    ) where
+        F: Fn(&T) -> bool,
+    {
+        let mut curr_node = Some(root);
+        while let Some(node) = curr_node {
+            if in_range(&(*node.as_ptr()).value) {
+                stack.push(node);
+                curr_node = (*node.as_ptr()).right;
+            } else {
+                curr_node = (*node.as_ptr()).left;
+            }
+        }
+    }
+
+    /// Recomputes the height of `node` from the cached heights of its children, so the
+    /// children have to be up to date
+    fn update_height(node: NonNull<Node<T>>) {
+        unsafe {
+            let left_height = Self::height_of((*node.as_ptr()).left);
+            let right_height = Self::height_of((*node.as_ptr()).right);
+            (*node.as_ptr()).height = left_height.max(right_height) + 1;
+        }
+    }
+
+    fn height_of(node: Option<NonNull<Node<T>>>) -> u64 {
+        match node {
+            Some(node) => unsafe { (*node.as_ptr()).height },
+            None => 0,
+        }
+    }
+
+    fn update_size(node: NonNull<Node<T>>) {
+        unsafe {
+            (*node.as_ptr()).size =
+                Self::size_of((*node.as_ptr()).left) + Self::size_of((*node.as_ptr()).right) + 1;
+        }
+    }
+
+    fn size_of(node: Option<NonNull<Node<T>>>) -> usize {
+        match node {
+            Some(node) => unsafe { (*node.as_ptr()).size },
+            None => 0,
+        }
+    }
+
+    /// Counts the values that `pred` holds for, given that it holds for all values up to
+    /// some point and for none after it
+    fn count_where<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut count = 0;
+        let mut curr_node = self.root;
+
+        while let Some(node) = curr_node {
+            unsafe {
+                if pred(&(*node.as_ptr()).value) {
+                    count += Self::size_of((*node.as_ptr()).left) + 1;
+                    curr_node = (*node.as_ptr()).right;
+                } else {
+                    curr_node = (*node.as_ptr()).left;
+                }
+            }
+        }
+
+        return count;
     }
 
     unsafe fn apply_rotation(node: NonNull<Node<T>>) -> NonNull<Node<T>> {
@@ -409,112 +1333,463 @@ where
         (*right.as_ptr()).left = Some(node);
         (*node.as_ptr()).right = center;
 
-        Self::update_height(node);
-        Self::update_height(right);
+        Self::update_height(node);
+        Self::update_size(node);
+        Self::update_height(right);
+        Self::update_size(right);
+
+        return right;
+    }
+
+    unsafe fn rotate_right(node: NonNull<Node<T>>) -> NonNull<Node<T>> {
+        let left = (*node.as_ptr()).left.expect("Left node should exit");
+        let center = (*left.as_ptr()).right;
+
+        (*left.as_ptr()).right = Some(node);
+        (*node.as_ptr()).left = center;
+
+        Self::update_height(node);
+        Self::update_size(node);
+        Self::update_height(left);
+        Self::update_size(left);
+
+        return left;
+    }
+
+    unsafe fn get_balance_factor(root: NonNull<Node<T>>) -> i64 {
+        let left_height = Self::height_of((*root.as_ptr()).left);
+        let right_height = Self::height_of((*root.as_ptr()).right);
+
+        left_height as i64 - right_height as i64
+    }
+}
+
+impl<T> Drop for AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn drop(&mut self) {
+        if let Some(root) = self.root.take() {
+            unsafe { Self::drop_subtree(root) };
+        }
+    }
+}
+
+impl<T> OrderedSet<T> for AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        return AVLTree::insert(self, value);
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        return AVLTree::remove(self, value);
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        return AVLTree::contains(self, value);
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        return AVLTree::iter(self);
+    }
+
+    fn min(&self) -> Option<&T> {
+        return AVLTree::min(self);
+    }
+
+    fn max(&self) -> Option<&T> {
+        return AVLTree::max(self);
+    }
+
+    fn len(&self) -> usize {
+        return AVLTree::len(self);
+    }
+}
+
+impl<T> Default for AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for AVLTree<T>
+where
+    T: PartialEq + PartialOrd + Clone,
+{
+    /// Clones the tree node by node in O(n), keeping its exact shape
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.map(|root| unsafe { Self::clone_subtree(root) }),
+            len: self.len,
+            mark: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    /// Trees are equal if they hold equal values, no matter their shapes
+    fn eq(&self, other: &Self) -> bool {
+        return self.len == other.len && self.iter().eq(other.iter());
+    }
+}
+
+impl<T> Eq for AVLTree<T> where T: Eq + PartialOrd {}
+
+impl<T> Debug for AVLTree<T>
+where
+    T: PartialEq + PartialOrd + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> FromIterator<T> for AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        return AVLTree::from_iter(iter);
+    }
+}
+
+impl<T> Extend<T> for AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> IntoIterator for AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return IntoIter { tree: self };
+    }
+}
+
+impl<'a, T> IntoIterator for &'a AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut AVLTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter_mut();
+    }
+}
+
+/// The state of an *inorder* traversal that walks towards the middle from both ends.
+/// `front` holds the nodes whose values and right subtrees are still to be visited, the
+/// next one on top, and `back` mirrors it for the left subtrees.
+struct Inorder<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    front: Vec<NonNull<Node<T>>>,
+    back: Vec<NonNull<Node<T>>>,
+}
+
+impl<T> Inorder<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn new(root: Option<NonNull<Node<T>>>) -> Self {
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        if let Some(root) = root {
+            unsafe {
+                AVLTree::push_front_path(root, &mut front, |_| true);
+                AVLTree::push_back_path(root, &mut back, |_| true);
+            }
+        }
+
+        Self { front, back }
+    }
+
+    unsafe fn next(&mut self) -> Option<NonNull<Node<T>>> {
+        let node = self.front.pop()?;
+
+        // Both ends met at the last node
+        if self.back.last() == Some(&node) {
+            self.front.clear();
+            self.back.clear();
+        } else if let Some(right) = (*node.as_ptr()).right {
+            AVLTree::push_front_path(right, &mut self.front, |_| true);
+        }
+
+        return Some(node);
+    }
+
+    unsafe fn next_back(&mut self) -> Option<NonNull<Node<T>>> {
+        let node = self.back.pop()?;
+
+        if self.front.last() == Some(&node) {
+            self.front.clear();
+            self.back.clear();
+        } else if let Some(left) = (*node.as_ptr()).left {
+            AVLTree::push_back_path(left, &mut self.back, |_| true);
+        }
+
+        return Some(node);
+    }
+}
+
+pub struct Iter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    range: Range<'a, T>,
+    len: usize,
+    visited: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.range.next()?;
+        self.visited += 1;
+        return Some(value);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.len - self.visited;
+        return (size, Some(size));
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let value = self.range.next_back()?;
+        self.visited += 1;
+        return Some(value);
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> where T: PartialEq + PartialOrd {}
+impl<T> FusedIterator for Iter<'_, T> where T: PartialEq + PartialOrd {}
+
+pub struct Range<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    nodes: Inorder<T>,
+    // NOTE: Find out if it is correct to use `PhantomData<&'a T>` or
+    // `PhantomData<&'a AVLTree<T>>`
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Range<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe { self.nodes.next().map(|node| &(*node.as_ptr()).value) }
+    }
+}
 
-        return right;
+impl<'a, T> DoubleEndedIterator for Range<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        unsafe { self.nodes.next_back().map(|node| &(*node.as_ptr()).value) }
     }
+}
 
-    unsafe fn rotate_right(node: NonNull<Node<T>>) -> NonNull<Node<T>> {
-        let left = (*node.as_ptr()).left.expect("Left node should exit");
-        let center = (*left.as_ptr()).right;
+impl<T> FusedIterator for Range<'_, T> where T: PartialEq + PartialOrd {}
 
-        (*left.as_ptr()).right = Some(node);
-        (*node.as_ptr()).left = center;
+pub struct IterMut<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    nodes: Inorder<T>,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
 
-        Self::update_height(node);
-        Self::update_height(left);
+impl<'a, T> Iterator for IterMut<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a mut T;
 
-        return left;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = unsafe { self.nodes.next()? };
+        self.len -= 1;
+
+        // Every node is visited only once, so the mutable references never alias
+        unsafe { Some(&mut (*node.as_ptr()).value) }
     }
 
-    unsafe fn get_balance_factor(root: NonNull<Node<T>>) -> i64 {
-        unsafe {
-            let left_height = if let Some(left) = (*root.as_ptr()).left {
-                (*left.as_ptr()).height
-            } else {
-                0
-            };
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.len, Some(self.len));
+    }
+}
 
-            let right_height = if let Some(right) = (*root.as_ptr()).right {
-                (*right.as_ptr()).height
-            } else {
-                0
-            };
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = unsafe { self.nodes.next_back()? };
+        self.len -= 1;
 
-            left_height as i64 - right_height as i64
-        }
+        unsafe { Some(&mut (*node.as_ptr()).value) }
     }
 }
 
-impl<T> Drop for AVLTree<T>
+impl<T> ExactSizeIterator for IterMut<'_, T> where T: PartialEq + PartialOrd {}
+impl<T> FusedIterator for IterMut<'_, T> where T: PartialEq + PartialOrd {}
+
+/// Takes the values out of the tree *inorder* by popping its smallest and largest values
+pub struct IntoIter<T>
 where
     T: PartialEq + PartialOrd,
 {
-    fn drop(&mut self) {
-        unsafe fn drop_recur<T>(node: NonNull<Node<T>>)
-        where
-            T: PartialEq + PartialOrd,
-        {
-            if let Some(left) = (*node.as_ptr()).left {
-                drop_recur(left);
-            }
+    tree: AVLTree<T>,
+}
 
-            if let Some(right) = (*node.as_ptr()).right {
-                drop_recur(right);
-            }
+impl<T> Iterator for IntoIter<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = T;
 
-            let _ = Box::from_raw(node.as_ptr());
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.tree.pop_min();
+    }
 
-        match self.root.take() {
-            Some(root) => unsafe { drop_recur(root) },
-            None => return,
-        };
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.tree.len, Some(self.tree.len));
     }
 }
 
-pub struct Iter<'a, T>
+impl<T> DoubleEndedIterator for IntoIter<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        return self.tree.pop_max();
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> where T: PartialEq + PartialOrd {}
+impl<T> FusedIterator for IntoIter<T> where T: PartialEq + PartialOrd {}
+
+pub struct PreorderIter<'a, T>
 where
     T: PartialEq + PartialOrd,
 {
     stack: Vec<NonNull<Node<T>>>,
-    current: Option<NonNull<Node<T>>>,
     len: usize,
-    visited: usize,
-    // NOTE: Find out if it is correct to use `PhantomData<&'a T>` or
-    // `PhantomData<&'a AVLTree<T>>`
     marker: PhantomData<&'a T>,
 }
 
-impl<'a, T> Iterator for Iter<'a, T>
+impl<'a, T> Iterator for PreorderIter<'a, T>
 where
-    T: PartialEq + PartialOrd + Debug,
+    T: PartialEq + PartialOrd,
 {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.visited == self.len {
-            return None;
-        }
+        let node = self.stack.pop()?;
+        self.len -= 1;
 
-        if self.stack.is_empty() && self.current.is_none() {
-            return None;
+        unsafe {
+            // Push the right child first so that the left subtree is visited before it
+            self.stack.extend((*node.as_ptr()).right);
+            self.stack.extend((*node.as_ptr()).left);
+
+            return Some(&(*node.as_ptr()).value);
         }
+    }
 
-        unsafe {
-            while let Some(left) = self.current {
-                self.stack.push(left);
-                self.current = (*left.as_ptr()).left;
-            }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.len, Some(self.len));
+    }
+}
+
+impl<T> ExactSizeIterator for PreorderIter<'_, T> where T: PartialEq + PartialOrd {}
+impl<T> FusedIterator for PreorderIter<'_, T> where T: PartialEq + PartialOrd {}
 
-            self.current = self.stack.pop();
+/// Keeps every node on the stack until both of its subtrees have been visited. The flag
+/// tells whether the children of the node have been pushed already.
+pub struct PostorderIter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    stack: Vec<(NonNull<Node<T>>, bool)>,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for PostorderIter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a T;
 
-            if let Some(current) = self.current {
-                self.current = (*current.as_ptr()).right;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, children_pushed)) = self.stack.pop() {
+            unsafe {
+                if children_pushed {
+                    self.len -= 1;
+                    return Some(&(*node.as_ptr()).value);
+                }
 
-                self.visited += 1;
-                return Some(&(*current.as_ptr()).value);
+                self.stack.push((node, true));
+                self.stack
+                    .extend((*node.as_ptr()).right.map(|right| (right, false)));
+                self.stack
+                    .extend((*node.as_ptr()).left.map(|left| (left, false)));
             }
         }
 
@@ -522,14 +1797,55 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.len - self.visited;
-        return (size, Some(size));
+        return (self.len, Some(self.len));
+    }
+}
+
+impl<T> ExactSizeIterator for PostorderIter<'_, T> where T: PartialEq + PartialOrd {}
+impl<T> FusedIterator for PostorderIter<'_, T> where T: PartialEq + PartialOrd {}
+
+pub struct LevelOrderIter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    queue: VecDeque<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for LevelOrderIter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        self.len -= 1;
+
+        unsafe {
+            self.queue.extend((*node.as_ptr()).left);
+            self.queue.extend((*node.as_ptr()).right);
+
+            return Some(&(*node.as_ptr()).value);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.len, Some(self.len));
     }
 }
 
+impl<T> ExactSizeIterator for LevelOrderIter<'_, T> where T: PartialEq + PartialOrd {}
+impl<T> FusedIterator for LevelOrderIter<'_, T> where T: PartialEq + PartialOrd {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeSet;
 
     #[test]
     fn can_insert() {
@@ -573,7 +1889,7 @@ mod tests {
         }
 
         assert_eq!(tree.len(), 8);
-        assert_eq!(tree.preorder_to_vec(), [5, 2, 1, 3, 6, 8, 7, 9]);
+        assert_eq!(tree.preorder_to_vec(), [5, 2, 1, 3, 8, 6, 7, 9]);
     }
 
     #[test]
@@ -599,6 +1915,442 @@ mod tests {
         assert_eq!(tree.preorder_to_vec(), [13, 9, 7, 10, 15, 20])
     }
 
+    #[test]
+    fn can_ignore_duplicates() {
+        let mut tree = AVLTree::from_iter([4, 2, 6, 1, 3, 5, 7]);
+        assert_eq!(tree.len(), 7);
+
+        assert!(!tree.insert(4));
+        assert!(!tree.insert(1));
+        assert!(tree.insert(8));
+        assert_eq!(tree.len(), 8);
+        assert_eq!(tree.inorder_to_vec(), [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn can_remove_missing_value() {
+        let mut tree = AVLTree::from_iter([2, 1, 3]);
+        assert_eq!(tree.remove(&4), None);
+        assert_eq!(tree.len(), 3);
+
+        let mut tree = AVLTree::from_iter([1.0, 2.0]);
+        assert_eq!(tree.remove(&f64::NAN), None);
+        assert!(!tree.contains(&f64::NAN));
+        assert_eq!(tree.len(), 2);
+
+        let mut tree: AVLTree<i32> = AVLTree::new();
+        assert_eq!(tree.remove(&1), None);
+        assert_eq!(tree.len(), 0);
+    }
+
+    #[test]
+    fn can_get_min_and_max() {
+        let tree = AVLTree::from_iter([5, 3, 8, 1, 9]);
+        assert_eq!(tree.min(), Some(&1));
+        assert_eq!(tree.max(), Some(&9));
+
+        let tree: AVLTree<i32> = AVLTree::new();
+        assert_eq!(tree.min(), None);
+        assert_eq!(tree.max(), None);
+    }
+
+    #[test]
+    fn can_remove_node_with_two_children() {
+        // The successor of 2 is its immediate right child
+        let mut tree = AVLTree::from_iter([2, 1, 3, 4]);
+        assert_eq!(tree.remove(&2), Some(2));
+        tree.validate().unwrap();
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), [1, 3, 4]);
+
+        // The successor of 4 has a right subtree that has to be kept
+        let mut tree = AVLTree::from_iter([4, 2, 8, 1, 3, 6, 10, 5, 7, 9, 11, 12, 6]);
+        assert_eq!(tree.remove(&8), Some(8));
+        assert_eq!(tree.remove(&4), Some(4));
+        tree.validate().unwrap();
+        assert_eq!(
+            tree.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 5, 6, 7, 9, 10, 11, 12]
+        );
+    }
+
+    #[test]
+    fn can_match_btree_set_after_random_operations() {
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut tree = AVLTree::new();
+            let mut set = BTreeSet::new();
+
+            for _ in 0..1000 {
+                let value = rng.gen_range(0..200);
+                if rng.gen_bool(0.6) {
+                    assert_eq!(tree.insert(value), set.insert(value));
+                } else {
+                    assert_eq!(tree.remove(&value), set.take(&value));
+                }
+
+                tree.validate().unwrap();
+                assert_eq!(tree.len(), set.len());
+            }
+
+            assert!(tree.iter().eq(set.iter()));
+            for value in 0..200 {
+                assert_eq!(tree.contains(&value), set.contains(&value));
+            }
+
+            // Drain the tree again to also cover removals down to an empty tree
+            let mut values: Vec<_> = set.iter().copied().collect();
+            values.shuffle(&mut rng);
+            for value in values {
+                assert_eq!(tree.remove(&value), Some(value));
+                tree.validate().unwrap();
+            }
+            assert!(tree.is_empty());
+        }
+    }
+
+    #[test]
+    fn can_find_neighbours() {
+        let tree = AVLTree::from_iter([10, 20, 30, 40, 50]);
+
+        assert_eq!(tree.floor(&30), Some(&30));
+        assert_eq!(tree.floor(&35), Some(&30));
+        assert_eq!(tree.floor(&5), None);
+        assert_eq!(tree.ceiling(&30), Some(&30));
+        assert_eq!(tree.ceiling(&35), Some(&40));
+        assert_eq!(tree.ceiling(&55), None);
+
+        assert_eq!(tree.predecessor(&30), Some(&20));
+        assert_eq!(tree.predecessor(&10), None);
+        assert_eq!(tree.successor(&30), Some(&40));
+        assert_eq!(tree.successor(&50), None);
+        assert_eq!(tree.successor(&0), Some(&10));
+    }
+
+    #[test]
+    fn can_pop_min_and_max() {
+        let mut tree = AVLTree::from_iter([4, 2, 6, 1, 3, 5, 7]);
+
+        assert_eq!(tree.pop_min(), Some(1));
+        assert_eq!(tree.pop_max(), Some(7));
+        assert_eq!(tree.pop_min(), Some(2));
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.min(), Some(&3));
+        assert_eq!(tree.max(), Some(&6));
+
+        let mut popped = Vec::new();
+        while let Some(value) = tree.pop_max() {
+            popped.push(value);
+        }
+        assert_eq!(popped, [6, 5, 4, 3]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.pop_min(), None);
+    }
+
+    #[test]
+    fn can_iter_range() {
+        let tree = AVLTree::from_iter((0..20).map(|i| i * 2));
+
+        let values: Vec<_> = tree.range(5..=11).copied().collect();
+        assert_eq!(values, [6, 8, 10]);
+        let values: Vec<_> = tree.range(6..10).copied().collect();
+        assert_eq!(values, [6, 8]);
+        let values: Vec<_> = tree
+            .range((Bound::Excluded(6), Bound::Included(10)))
+            .copied()
+            .collect();
+        assert_eq!(values, [8, 10]);
+        let values: Vec<_> = tree.range(..4).copied().collect();
+        assert_eq!(values, [0, 2]);
+        let values: Vec<_> = tree.range(35..).copied().collect();
+        assert_eq!(values, [36, 38]);
+        assert_eq!(tree.range(..).count(), 20);
+
+        // Empty ranges
+        assert_eq!(tree.range(7..8).next(), None);
+        assert_eq!(tree.range(10..10).next(), None);
+        assert_eq!(
+            tree.range((Bound::Included(12), Bound::Excluded(4))).next(),
+            None
+        );
+        assert_eq!(tree.range(100..).next(), None);
+        assert_eq!(tree.range(..0).next_back(), None);
+        assert_eq!(AVLTree::<i32>::new().range(..).next(), None);
+    }
+
+    #[test]
+    fn can_iter_range_from_both_ends() {
+        let tree = AVLTree::from_iter(0..50);
+
+        for start in 0..12 {
+            for end in start..12 {
+                let mut range = tree.range(start * 4..end * 4 + 1);
+                let mut values = Vec::new();
+                let mut back_values = Vec::new();
+
+                // Alternate between both ends until they meet
+                while let Some(value) = range.next() {
+                    values.push(*value);
+                    match range.next_back() {
+                        Some(value) => back_values.push(*value),
+                        None => break,
+                    }
+                }
+                assert_eq!(range.next(), None);
+                assert_eq!(range.next_back(), None);
+
+                values.extend(back_values.into_iter().rev());
+                assert_eq!(values, (start * 4..end * 4 + 1).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn can_select_and_rank() {
+        let mut tree = AVLTree::from_iter((0..100).map(|i| (i * 37) % 100 * 2));
+        tree.pop_min();
+        tree.pop_max();
+        let values: Vec<_> = (1..99).map(|i| i * 2).collect();
+
+        for (k, value) in values.iter().enumerate() {
+            assert_eq!(tree.select(k), Some(value));
+            assert_eq!(tree.rank(value), k);
+            assert_eq!(tree.rank(&(value + 1)), k + 1);
+        }
+        assert_eq!(tree.select(values.len()), None);
+        assert_eq!(tree.rank(&0), 0);
+        assert_eq!(tree.rank(&1000), values.len());
+    }
+
+    #[test]
+    fn can_count_range() {
+        let tree = AVLTree::from_iter((0..50).map(|i| i * 2));
+
+        assert_eq!(tree.count_range(..), 50);
+        assert_eq!(tree.count_range(10..20), 5);
+        assert_eq!(tree.count_range(10..=20), 6);
+        assert_eq!(tree.count_range(11..=19), 4);
+        assert_eq!(
+            tree.count_range((Bound::Excluded(10), Bound::Unbounded)),
+            44
+        );
+        assert_eq!(tree.count_range(..0), 0);
+        assert_eq!(tree.count_range(90..), 5);
+        assert_eq!(tree.count_range(13..13), 0);
+        assert_eq!(
+            tree.count_range((Bound::Included(30), Bound::Excluded(20))),
+            0
+        );
+
+        for start in 0..30 {
+            for end in start..30 {
+                assert_eq!(tree.count_range(start..end), tree.range(start..end).count());
+            }
+        }
+    }
+
+    #[test]
+    fn can_build_from_sorted_iter() {
+        for len in 0..70 {
+            let tree = AVLTree::from_sorted_iter(0..len);
+            tree.validate().unwrap();
+            assert!(tree.iter().copied().eq(0..len));
+
+            // A perfectly balanced tree is as low as possible
+            let min_height = (usize::BITS - (len as usize).leading_zeros()) as u64;
+            assert_eq!(tree.height(), min_height);
+        }
+
+        let tree = AVLTree::from_sorted_iter([1, 1, 2, 3, 3, 3]);
+        tree.validate().unwrap();
+        assert_eq!(tree.inorder_to_vec(), [1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn can_reject_unsorted_values() {
+        AVLTree::from_sorted_iter([1, 3, 2]);
+    }
+
+    #[test]
+    fn can_split_and_join() {
+        let tree = AVLTree::from_iter(0..100);
+
+        let (less, equal, greater) = tree.split(&40);
+        less.validate().unwrap();
+        greater.validate().unwrap();
+        assert_eq!(equal, Some(40));
+        assert!(less.iter().copied().eq(0..40));
+        assert!(greater.iter().copied().eq(41..100));
+
+        let tree = less.join(greater);
+        tree.validate().unwrap();
+        assert!(tree.iter().copied().eq((0..100).filter(|v| *v != 40)));
+
+        let (less, equal, greater) = tree.split(&40);
+        assert_eq!(equal, None);
+        assert_eq!((less.len(), greater.len()), (40, 59));
+
+        // Joining trees of very different heights
+        let tree = AVLTree::from_iter([-1]).join(less).join(AVLTree::new());
+        let tree = AVLTree::new()
+            .join(tree)
+            .join(greater)
+            .join(AVLTree::from_iter([500]));
+        tree.validate().unwrap();
+        assert_eq!(tree.len(), 101);
+        assert_eq!((tree.min(), tree.max()), (Some(&-1), Some(&500)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn can_reject_overlapping_join() {
+        AVLTree::from_iter([1, 5]).join(AVLTree::from_iter([3, 7]));
+    }
+
+    #[test]
+    fn can_combine_sets() {
+        let a = || AVLTree::from_iter([1, 2, 3, 4, 5, 6]);
+        let b = || AVLTree::from_iter([4, 5, 6, 7, 8]);
+
+        assert_eq!(a().union(b()).inorder_to_vec(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(a().intersection(b()).inorder_to_vec(), [4, 5, 6]);
+        assert_eq!(a().difference(b()).inorder_to_vec(), [1, 2, 3]);
+        assert_eq!(b().difference(a()).inorder_to_vec(), [7, 8]);
+
+        assert_eq!(a().union(AVLTree::new()).len(), 6);
+        assert_eq!(AVLTree::new().intersection(b()).len(), 0);
+        assert_eq!(a().difference(AVLTree::new()).len(), 6);
+    }
+
+    #[test]
+    fn can_combine_random_sets_like_btree_set() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..50 {
+            let a_len = rng.gen_range(0..200);
+            let b_len = rng.gen_range(0..200);
+            let a_values: BTreeSet<i32> = (0..a_len).map(|_| rng.gen_range(0..300)).collect();
+            let b_values: BTreeSet<i32> = (0..b_len).map(|_| rng.gen_range(0..300)).collect();
+            let a = || AVLTree::from_iter(a_values.iter().copied());
+            let b = || AVLTree::from_iter(b_values.iter().copied());
+
+            let union = a().union(b());
+            union.validate().unwrap();
+            assert!(union.iter().eq(a_values.union(&b_values)));
+
+            let intersection = a().intersection(b());
+            intersection.validate().unwrap();
+            assert!(intersection.iter().eq(a_values.intersection(&b_values)));
+
+            let difference = a().difference(b());
+            difference.validate().unwrap();
+            assert!(difference.iter().eq(a_values.difference(&b_values)));
+        }
+    }
+
+    #[test]
+    fn can_validate() {
+        assert_eq!(AVLTree::<i32>::new().validate(), Ok(()));
+        assert_eq!(AVLTree::from_iter(0..100).validate(), Ok(()));
+
+        let mut tree = AVLTree::from_iter([2, 1, 3, 4]);
+        let root = tree.root.unwrap();
+
+        unsafe {
+            let left = (*root.as_ptr()).left.unwrap();
+            let right = (*root.as_ptr()).right.unwrap();
+
+            (*left.as_ptr()).value = 10;
+            assert_eq!(
+                tree.validate(),
+                Err(InvariantViolation::Ordering {
+                    value: &10,
+                    ancestor: &2
+                })
+            );
+            (*left.as_ptr()).value = 1;
+
+            (*root.as_ptr()).height = 5;
+            assert_eq!(
+                tree.validate(),
+                Err(InvariantViolation::Height {
+                    value: &2,
+                    cached: 5,
+                    actual: 3
+                })
+            );
+            (*root.as_ptr()).height = 3;
+
+            (*root.as_ptr()).left = None;
+            let violation = tree.validate().unwrap_err();
+            assert_eq!(
+                violation,
+                InvariantViolation::Balance {
+                    value: &2,
+                    balance: -2
+                }
+            );
+            assert_eq!(violation.to_string(), "2 has a balance factor of -2");
+            (*root.as_ptr()).left = Some(left);
+
+            (*right.as_ptr()).size = 7;
+            assert_eq!(
+                tree.validate(),
+                Err(InvariantViolation::Size {
+                    value: &3,
+                    cached: 7,
+                    actual: 2
+                })
+            );
+            (*right.as_ptr()).size = 2;
+        }
+
+        tree.len = 5;
+        assert_eq!(
+            tree.validate(),
+            Err(InvariantViolation::Len { len: 5, actual: 4 })
+        );
+        tree.len = 4;
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    #[test]
+    fn can_export_dot() {
+        let tree = AVLTree::from_iter([2, 1, 3, 4]);
+        let expected = r#"digraph AVLTree {
+    node0 [label="2\nh: 3, b: -1"];
+    node1 [label="1\nh: 1, b: 0"];
+    node0 -> node1;
+    node2 [label="3\nh: 2, b: -1"];
+    null3 [shape=point];
+    node2 -> null3;
+    node4 [label="4\nh: 1, b: 0"];
+    node2 -> node4;
+    node0 -> node2;
+}
+"#;
+        assert_eq!(tree.to_dot(), expected);
+
+        let tree = AVLTree::from_iter(["a\"b"]);
+        assert!(tree.to_dot().contains(r#"label="\"a\\\"b\"\nh: 1, b: 0""#));
+        assert_eq!(AVLTree::<i32>::new().to_dot(), "digraph AVLTree {\n}\n");
+    }
+
+    #[test]
+    fn can_render_ascii() {
+        let tree = AVLTree::from_iter([4, 2, 6, 1, 3, 7]);
+        let expected = "\
+4
+├── L: 2
+│   ├── L: 1
+│   └── R: 3
+└── R: 6
+    └── R: 7
+";
+        assert_eq!(tree.to_ascii(), expected);
+        assert_eq!(AVLTree::<i32>::new().to_ascii(), "");
+    }
+
     #[test]
     fn can_iter() {
         let tree = AVLTree::from_iter([1, 2, 3, 4]);
@@ -611,6 +2363,128 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn can_iter_rev() {
+        let tree = AVLTree::from_iter([3, 1, 4, 2, 5]);
+        assert_eq!(
+            tree.iter().rev().copied().collect::<Vec<_>>(),
+            [5, 4, 3, 2, 1]
+        );
+
+        let mut iter = tree.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.copied().collect::<Vec<_>>(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn can_traverse_in_all_orders() {
+        let tree = AVLTree::from_iter([4, 2, 6, 1, 3, 5, 7]);
+
+        let preorder = tree.preorder_iter();
+        assert_eq!(preorder.len(), 7);
+        assert_eq!(preorder.copied().collect::<Vec<_>>(), [4, 2, 1, 3, 6, 5, 7]);
+        let postorder: Vec<_> = tree.postorder_iter().copied().collect();
+        assert_eq!(postorder, [1, 3, 2, 5, 7, 6, 4]);
+        let level_order: Vec<_> = tree.level_order_iter().copied().collect();
+        assert_eq!(level_order, [4, 2, 6, 1, 3, 5, 7]);
+
+        let tree = AVLTree::from_iter([9, 5, 10, 0, 6, 11, -1, 1, 2]);
+        let preorder: Vec<_> = tree.preorder_iter().copied().collect();
+        assert_eq!(preorder, tree.clone().preorder_to_vec());
+        let mut postorder = tree.postorder_iter();
+        postorder.next();
+        assert_eq!(postorder.size_hint(), (8, Some(8)));
+        assert_eq!(postorder.last(), Some(&9));
+
+        let tree: AVLTree<i32> = AVLTree::new();
+        assert_eq!(tree.preorder_iter().next(), None);
+        assert_eq!(tree.postorder_iter().next(), None);
+        assert_eq!(tree.level_order_iter().next(), None);
+    }
+
+    #[test]
+    fn can_iter_mut() {
+        let mut tree = AVLTree::from_iter(0..10);
+        for value in tree.iter_mut() {
+            *value *= 10;
+        }
+
+        let mut iter = tree.iter_mut();
+        assert_eq!(iter.len(), 10);
+        *iter.next_back().unwrap() += 5;
+        assert_eq!(iter.next(), Some(&mut 0));
+        assert_eq!(iter.len(), 8);
+
+        for value in &mut tree {
+            *value += 1;
+        }
+        let values: Vec<_> = tree.iter().copied().collect();
+        assert_eq!(values, [1, 11, 21, 31, 41, 51, 61, 71, 81, 96]);
+        assert!(tree.contains(&96));
+    }
+
+    #[test]
+    fn can_into_iter() {
+        let tree = AVLTree::from_iter([3, 1, 4, 5, 2]);
+        assert_eq!(tree.into_iter().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+        let mut iter = AVLTree::from_iter([3, 1, 4, 5, 2]).into_iter();
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), [4, 3, 2]);
+
+        let tree = AVLTree::from_iter(["a".to_string(), "b".to_string()]);
+        let mut total = String::new();
+        for value in &tree {
+            total.push_str(value);
+        }
+        for value in tree {
+            total.push_str(&value);
+        }
+        assert_eq!(total, "abab");
+    }
+
+    #[test]
+    fn can_clone_and_compare() {
+        let tree = AVLTree::from_iter([5, 3, 8, 1, 4]);
+        let mut clone = tree.clone();
+        clone.validate().unwrap();
+        assert_eq!(clone, tree);
+        assert_eq!(
+            clone.clone().preorder_to_vec(),
+            tree.clone().preorder_to_vec()
+        );
+
+        clone.insert(10);
+        assert_ne!(clone, tree);
+        assert_eq!(tree.len(), 5);
+
+        // Equality doesn't depend on the shape of the trees
+        let sorted = AVLTree::from_sorted_iter(0..20);
+        let inserted = AVLTree::from_iter((0..20).rev());
+        assert_ne!(
+            sorted.clone().preorder_to_vec(),
+            inserted.clone().preorder_to_vec()
+        );
+        assert_eq!(sorted, inserted);
+    }
+
+    #[test]
+    fn can_collect_and_extend() {
+        let mut tree: AVLTree<_> = (0..5).rev().collect();
+        tree.extend([3, 4, 5, 6]);
+        tree.validate().unwrap();
+        assert_eq!(tree.len(), 7);
+        assert_eq!(format!("{:?}", tree), "{0, 1, 2, 3, 4, 5, 6}");
+
+        let tree: AVLTree<i32> = AVLTree::default();
+        assert!(tree.is_empty());
+        assert_eq!(format!("{:?}", tree), "{}");
+    }
+
     #[test]
     fn can_iter_size_hint() {
         let tree = AVLTree::from_iter([1, 2, 3, 4]);
@@ -1,14 +1,14 @@
 use std::cmp;
+use std::marker::PhantomData;
+use std::mem;
 use std::{
     borrow::BorrowMut,
-    cell::RefCell,
-    fmt::Debug,
+    cell::{Ref, RefCell},
+    fmt::{Debug, Display},
     rc::{Rc, Weak},
 };
 
-// use super::DisplayLabel;
-
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct BackPointingBinaryTree<T>
 where
     T: Debug + PartialEq + PartialOrd + Clone,
@@ -17,13 +17,13 @@ where
     size: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 struct Node<T>
 where
     T: Debug + PartialEq + PartialOrd + Clone,
 {
     value: Option<T>,
-    _parent: Option<Weak<RefCell<Node<T>>>>,
+    parent: Option<Weak<RefCell<Node<T>>>>,
     left: Option<Rc<RefCell<Node<T>>>>,
     right: Option<Rc<RefCell<Node<T>>>>,
 }
@@ -59,7 +59,7 @@ where
             None => {
                 self.root = Some(Rc::new(RefCell::new(Node {
                     value: Some(value),
-                    _parent: None,
+                    parent: None,
                     left: None,
                     right: None,
                 })));
@@ -79,7 +79,7 @@ where
                     None => {
                         root_node.left = Some(Rc::new(RefCell::new(Node {
                             value: Some(value),
-                            _parent: Some(Rc::downgrade(&root)),
+                            parent: Some(Rc::downgrade(&root)),
                             left: None,
                             right: None,
                         })));
@@ -91,7 +91,7 @@ where
                     None => {
                         root_node.right = Some(Rc::new(RefCell::new(Node {
                             value: Some(value),
-                            _parent: Some(Rc::downgrade(&root)),
+                            parent: Some(Rc::downgrade(&root)),
                             left: None,
                             right: None,
                         })));
@@ -103,12 +103,67 @@ where
         }
     }
 
-    pub fn remove(&mut self, _value: T) {
-        todo!()
+    /// Removes one node holding `value`. A node with two children takes over the value of
+    /// its inorder predecessor, whose node is unlinked instead.
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let node = self.find_first(value)?;
+
+        let has_both_children = {
+            let node = node.as_ref().borrow();
+            node.left.is_some() && node.right.is_some()
+        };
+
+        let target = if has_both_children {
+            let left = node.as_ref().borrow().left.clone().unwrap();
+            let pred = Self::last_in(left);
+            mem::swap(
+                &mut node.as_ref().borrow_mut().value,
+                &mut pred.as_ref().borrow_mut().value,
+            );
+            pred
+        } else {
+            node
+        };
+
+        // `target` has at most one child, which takes its place below its parent
+        let (child, parent) = {
+            let mut target = target.as_ref().borrow_mut();
+            let child = target.left.take().or_else(|| target.right.take());
+            (
+                child,
+                target.parent.take().and_then(|parent| parent.upgrade()),
+            )
+        };
+
+        if let Some(child) = &child {
+            child.as_ref().borrow_mut().parent = parent.as_ref().map(Rc::downgrade);
+        }
+
+        match parent {
+            Some(parent) => {
+                let mut parent = parent.as_ref().borrow_mut();
+                let is_left = match &parent.left {
+                    Some(left) => Rc::ptr_eq(left, &target),
+                    None => false,
+                };
+
+                if is_left {
+                    parent.left = child;
+                } else {
+                    parent.right = child;
+                }
+            }
+            None => self.root = child,
+        }
+
+        self.size -= 1;
+        let value = target.as_ref().borrow_mut().value.take();
+        return value;
     }
 
     pub fn clear(&mut self) {
-        todo!()
+        self.root = None;
+        self.size = 0;
     }
 
     pub fn size(&self) -> usize {
@@ -162,79 +217,427 @@ where
     }
 
     pub fn to_vec_pre_order(self) -> Vec<T> {
-        todo!()
+        let mut vec = Vec::with_capacity(self.size);
+
+        if let Some(root) = self.root {
+            Self::to_vec_pre_order_recur(root.clone(), &mut vec);
+        }
+
+        return vec;
+    }
+
+    fn to_vec_pre_order_recur(root: Rc<RefCell<Node<T>>>, vec: &mut Vec<T>) {
+        let mut root = root.as_ref().borrow_mut();
+        vec.push(root.value.take().unwrap());
+
+        if let Some(left_node) = &root.left {
+            Self::to_vec_pre_order_recur(left_node.clone(), vec);
+        }
+
+        if let Some(right_node) = &root.right {
+            Self::to_vec_pre_order_recur(right_node.clone(), vec);
+        }
     }
 
     pub fn to_vec_post_order(self) -> Vec<T> {
-        todo!()
+        let mut vec = Vec::with_capacity(self.size);
+
+        if let Some(root) = self.root {
+            Self::to_vec_post_order_recur(root.clone(), &mut vec);
+        }
+
+        return vec;
+    }
+
+    fn to_vec_post_order_recur(root: Rc<RefCell<Node<T>>>, vec: &mut Vec<T>) {
+        let mut root = root.as_ref().borrow_mut();
+        if let Some(left_node) = &root.left {
+            Self::to_vec_post_order_recur(left_node.clone(), vec);
+        }
+
+        if let Some(right_node) = &root.right {
+            Self::to_vec_post_order_recur(right_node.clone(), vec);
+        }
+
+        vec.push(root.value.take().unwrap());
+    }
+
+    /// Returns the smallest value greater than `value`, walking up through the parents
+    /// from the last node holding `value` if needed. Returns `None` if `value` isn't in
+    /// the tree.
+    pub fn successor(&self, value: &T) -> Option<T> {
+        let node = Self::next_node(self.find_last(value)?)?;
+        let value = node.as_ref().borrow().value.clone();
+        return value;
+    }
+
+    /// Returns the largest value less than `value`, walking up through the parents from
+    /// the first node holding `value` if needed. Returns `None` if `value` isn't in the
+    /// tree.
+    pub fn predecessor(&self, value: &T) -> Option<T> {
+        let node = Self::prev_node(self.find_first(value)?)?;
+        let value = node.as_ref().borrow().value.clone();
+        return value;
+    }
+
+    /// Returns a cursor pointing at the root, or `None` if the tree is empty
+    pub fn cursor(&self) -> Option<Cursor<'_, T>> {
+        Some(Cursor {
+            current: self.root.clone()?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns a cursor pointing at the first node holding `value` in *inorder*
+    pub fn cursor_at(&self, value: &T) -> Option<Cursor<'_, T>> {
+        Some(Cursor {
+            current: self.find_first(value)?,
+            marker: PhantomData,
+        })
+    }
+}
+
+// private helper functions
+impl<T> BackPointingBinaryTree<T>
+where
+    T: Debug + PartialEq + PartialOrd + Clone,
+{
+    fn clone_subtree(
+        node: &Rc<RefCell<Node<T>>>,
+        parent: Option<Weak<RefCell<Node<T>>>>,
+    ) -> Rc<RefCell<Node<T>>> {
+        let node = node.as_ref().borrow();
+        let clone = Rc::new(RefCell::new(Node {
+            value: node.value.clone(),
+            parent,
+            left: None,
+            right: None,
+        }));
+
+        // The children can only point back once the clone of their parent exists
+        let left = node
+            .left
+            .as_ref()
+            .map(|left| Self::clone_subtree(left, Some(Rc::downgrade(&clone))));
+        let right = node
+            .right
+            .as_ref()
+            .map(|right| Self::clone_subtree(right, Some(Rc::downgrade(&clone))));
+
+        let mut clone_ref = clone.as_ref().borrow_mut();
+        clone_ref.left = left;
+        clone_ref.right = right;
+        drop(clone_ref);
+
+        return clone;
+    }
+
+    /// Returns the first node holding `value` in *inorder*
+    fn find_first(&self, value: &T) -> Option<Rc<RefCell<Node<T>>>> {
+        let mut found = None;
+        let mut curr_node = self.root.clone();
+
+        while let Some(node) = curr_node {
+            let node_ref = node.as_ref().borrow();
+            if node_ref.value.as_ref().unwrap() >= value {
+                curr_node = node_ref.left.clone();
+                drop(node_ref);
+                found = Some(node);
+            } else {
+                curr_node = node_ref.right.clone();
+            }
+        }
+
+        return found.filter(|node| node.as_ref().borrow().value.as_ref() == Some(value));
+    }
+
+    /// Returns the last node holding `value` in *inorder*
+    fn find_last(&self, value: &T) -> Option<Rc<RefCell<Node<T>>>> {
+        let mut found = None;
+        let mut curr_node = self.root.clone();
+
+        while let Some(node) = curr_node {
+            let node_ref = node.as_ref().borrow();
+            if node_ref.value.as_ref().unwrap() <= value {
+                curr_node = node_ref.right.clone();
+                drop(node_ref);
+                found = Some(node);
+            } else {
+                curr_node = node_ref.left.clone();
+            }
+        }
+
+        return found.filter(|node| node.as_ref().borrow().value.as_ref() == Some(value));
+    }
+
+    fn first_in(root: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        let mut node = root;
+        loop {
+            let left = node.as_ref().borrow().left.clone();
+            match left {
+                Some(left) => node = left,
+                None => return node,
+            }
+        }
+    }
+
+    fn last_in(root: Rc<RefCell<Node<T>>>) -> Rc<RefCell<Node<T>>> {
+        let mut node = root;
+        loop {
+            let right = node.as_ref().borrow().right.clone();
+            match right {
+                Some(right) => node = right,
+                None => return node,
+            }
+        }
+    }
+
+    fn parent_of(node: &Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
+        let parent = node.as_ref().borrow().parent.as_ref()?.upgrade();
+        return parent;
+    }
+
+    /// Returns the node after `node` in *inorder*, which is either the first node of its
+    /// right subtree or the closest ancestor that has `node` in its left subtree
+    fn next_node(node: Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
+        let right = node.as_ref().borrow().right.clone();
+        if let Some(right) = right {
+            return Some(Self::first_in(right));
+        }
+
+        let mut node = node;
+        loop {
+            let parent = Self::parent_of(&node)?;
+            let is_left = match &parent.as_ref().borrow().left {
+                Some(left) => Rc::ptr_eq(left, &node),
+                None => false,
+            };
+
+            if is_left {
+                return Some(parent);
+            }
+            node = parent;
+        }
+    }
+
+    /// Returns the node before `node` in *inorder*, which is either the last node of its
+    /// left subtree or the closest ancestor that has `node` in its right subtree
+    fn prev_node(node: Rc<RefCell<Node<T>>>) -> Option<Rc<RefCell<Node<T>>>> {
+        let left = node.as_ref().borrow().left.clone();
+        if let Some(left) = left {
+            return Some(Self::last_in(left));
+        }
+
+        let mut node = node;
+        loop {
+            let parent = Self::parent_of(&node)?;
+            let is_right = match &parent.as_ref().borrow().right {
+                Some(right) => Rc::ptr_eq(right, &node),
+                None => false,
+            };
+
+            if is_right {
+                return Some(parent);
+            }
+            node = parent;
+        }
     }
 }
 
-// struct NodeDisplayFormat {
-//     label: String,
-//     pre_padding: usize,
-//     post_padding: usize,
-// }
-//
-// impl<T> BackPointingBinaryTree<T>
-// where
-//     T: Debug + PartialEq + PartialOrd + Clone + DisplayLabel,
-// {
-//     pub fn get_display_tree_structs(self) -> Vec<Vec<NodeDisplayFormat>> {
-//         let mut vec = Vec::new();
-//
-//         if let Some(root) = self.root {
-//             Self::get_display_tree_struct_recur(root.clone(), &mut vec);
-//         }
-//
-//         return vec;
-//     }
-//
-//     fn get_display_tree_struct_recur(root: Rc<RefCell<Node<T>>>, vec: &mut Vec<Vec<NodeDisplayFormat>) {
-//         let mut root = root.as_ref().borrow_mut();
-//         if let Some(left_node) = &root.left {
-//             Self::get_display_tree_struct_recur(left_node.clone(), vec);
-//         }
-//
-//         // TODO:
-//         let label = root.value.take().unwrap().label();
-//         vec.push(NodeDisplayFormat {
-//             label,
-//             pre_padding: 0,
-//             post_padding: 0,
-//         });
-//
-//         if let Some(right_node) = &root.right {
-//             Self::get_display_tree_struct_recur(right_node.clone(), vec);
-//         }
-//     }
-// }
-//
-// impl<T> Display for BackPointingBinaryTree<T>
-// where
-//     T: Debug + PartialEq + PartialOrd + Clone + Copy + DisplayLabel,
-// {
-//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//         let vec = self.clone().get_display_tree_structs();
-//         for item in vec {
-//             let label = item.label();
-//             f.write_fmt(format_args!("({}, {}), ", label, label.len()))?;
-//         }
-//
-//         Ok(())
-//     }
-// }
+impl<T> Clone for BackPointingBinaryTree<T>
+where
+    T: Debug + PartialEq + PartialOrd + Clone,
+{
+    /// Clones every node, so the clone shares nothing with the original
+    fn clone(&self) -> Self {
+        Self {
+            root: self
+                .root
+                .as_ref()
+                .map(|root| Self::clone_subtree(root, None)),
+            size: self.size,
+        }
+    }
+}
+
+/// A read-only cursor that can walk the tree in any direction, including up through the
+/// parent links and along the *inorder* sequence
+pub struct Cursor<'a, T>
+where
+    T: Debug + PartialEq + PartialOrd + Clone,
+{
+    current: Rc<RefCell<Node<T>>>,
+    marker: PhantomData<&'a BackPointingBinaryTree<T>>,
+}
+
+impl<'a, T> Cursor<'a, T>
+where
+    T: Debug + PartialEq + PartialOrd + Clone,
+{
+    pub fn current(&self) -> Ref<'_, T> {
+        Ref::map(self.current.as_ref().borrow(), |node| {
+            node.value.as_ref().unwrap()
+        })
+    }
+
+    pub fn is_root(&self) -> bool {
+        return BackPointingBinaryTree::parent_of(&self.current).is_none();
+    }
+
+    /// Moves to the parent and returns `true`, or stays put and returns `false` at the root
+    pub fn move_up(&mut self) -> bool {
+        let parent = BackPointingBinaryTree::parent_of(&self.current);
+        return self.move_to(parent);
+    }
+
+    /// Moves to the left child and returns `true`, or stays put and returns `false`
+    pub fn move_left(&mut self) -> bool {
+        let left = self.current.as_ref().borrow().left.clone();
+        return self.move_to(left);
+    }
+
+    /// Moves to the right child and returns `true`, or stays put and returns `false`
+    pub fn move_right(&mut self) -> bool {
+        let right = self.current.as_ref().borrow().right.clone();
+        return self.move_to(right);
+    }
+
+    /// Moves to the next node in *inorder* and returns `true`, or stays put and returns
+    /// `false` at the last node
+    pub fn move_next(&mut self) -> bool {
+        let next = BackPointingBinaryTree::next_node(self.current.clone());
+        return self.move_to(next);
+    }
+
+    /// Moves to the previous node in *inorder* and returns `true`, or stays put and
+    /// returns `false` at the first node
+    pub fn move_prev(&mut self) -> bool {
+        let prev = BackPointingBinaryTree::prev_node(self.current.clone());
+        return self.move_to(prev);
+    }
+
+    fn move_to(&mut self, node: Option<Rc<RefCell<Node<T>>>>) -> bool {
+        match node {
+            Some(node) => {
+                self.current = node;
+                return true;
+            }
+            None => return false,
+        }
+    }
+}
+
+/// The lines that draw a subtree and where its root label sits within them
+struct DisplayBox {
+    lines: Vec<String>,
+    width: usize,
+    root_start: usize,
+    root_end: usize,
+}
+
+impl<T> BackPointingBinaryTree<T>
+where
+    T: Debug + PartialEq + PartialOrd + Clone + Display,
+{
+    /// Draws the subtree by putting the boxes of both subtrees side by side below the label
+    /// of the root, which is connected to their roots with `_`, `/` and `\`
+    fn display_box(root: &Rc<RefCell<Node<T>>>) -> DisplayBox {
+        let root = root.as_ref().borrow();
+        let mut label = root.value.as_ref().unwrap().to_string();
+        // An empty label would give a leaf an empty box, which reads as a missing child
+        if label.is_empty() {
+            label.push(' ');
+        }
+        let label_width = label.chars().count();
+
+        let empty_box = || DisplayBox {
+            lines: Vec::new(),
+            width: 0,
+            root_start: 0,
+            root_end: 0,
+        };
+        let left = root.left.as_ref().map_or_else(empty_box, Self::display_box);
+        let right = root
+            .right
+            .as_ref()
+            .map_or_else(empty_box, Self::display_box);
+
+        let mut label_line = String::new();
+        let mut branch_line = String::new();
+        let mut gap_width = label_width;
+
+        let root_start = if left.width > 0 {
+            let left_root = (left.root_start + left.root_end) / 2 + 1;
+            label_line.push_str(&" ".repeat(left_root + 1));
+            label_line.push_str(&"_".repeat(left.width - left_root));
+            branch_line.push_str(&format!("{}/", " ".repeat(left_root)));
+            branch_line.push_str(&" ".repeat(left.width - left_root));
+
+            gap_width += 1;
+            left.width + 1
+        } else {
+            0
+        };
+
+        label_line.push_str(&label);
+        branch_line.push_str(&" ".repeat(label_width));
+
+        if right.width > 0 {
+            let right_root = (right.root_start + right.root_end) / 2;
+            label_line.push_str(&"_".repeat(right_root));
+            label_line.push_str(&" ".repeat(right.width - right_root + 1));
+            branch_line.push_str(&format!("{}\\", " ".repeat(right_root)));
+            branch_line.push_str(&" ".repeat(right.width - right_root));
+
+            gap_width += 1;
+        }
+
+        let mut lines = vec![label_line, branch_line];
+        for i in 0..left.lines.len().max(right.lines.len()) {
+            let left_line = left.lines.get(i).cloned();
+            let right_line = right.lines.get(i).cloned();
+
+            lines.push(format!(
+                "{}{}{}",
+                left_line.unwrap_or_else(|| " ".repeat(left.width)),
+                " ".repeat(gap_width),
+                right_line.unwrap_or_else(|| " ".repeat(right.width))
+            ));
+        }
+
+        DisplayBox {
+            width: lines[0].chars().count(),
+            lines,
+            root_start,
+            root_end: root_start + label_width - 1,
+        }
+    }
+}
+
+impl<T> Display for BackPointingBinaryTree<T>
+where
+    T: Debug + PartialEq + PartialOrd + Clone + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = match &self.root {
+            Some(root) => Self::display_box(root).lines,
+            None => Vec::new(),
+        };
+
+        let lines: Vec<_> = lines.iter().map(|line| line.trim_end()).collect();
+        let len = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |i| i + 1);
+
+        f.write_str(&lines[..len].join("\n"))
+    }
+}
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    // impl DisplayLabel for i32 {
-    //     fn label(&self) -> String {
-    //         self.to_string()
-    //     }
-    // }
-
     #[test]
     fn can_in_order_to_vec() {
         let tree =
@@ -255,13 +658,175 @@ mod test {
         assert_eq!(tree.height(), expected);
     }
 
-    // #[test]
-    // fn can_fmt_pretty() {
-    //     let tree =
-    //         BackPointingBinaryTree::from_iter(vec![6, 4, 8, 2, 5, 1, 3, 7, 9, 10].into_iter());
-    //
-    //     let expected = "".to_string();
-    //
-    //     assert_eq!(format!("{}", tree), expected);
-    // }
+    #[test]
+    fn can_pre_and_post_order_to_vec() {
+        let values = vec![6, 4, 8, 2, 5, 1, 3, 7, 9, 10];
+
+        let tree = BackPointingBinaryTree::from_iter(values.clone().into_iter());
+        assert_eq!(tree.to_vec_pre_order(), vec![6, 4, 2, 1, 3, 5, 8, 7, 9, 10]);
+
+        let tree = BackPointingBinaryTree::from_iter(values.into_iter());
+        assert_eq!(
+            tree.to_vec_post_order(),
+            vec![1, 3, 2, 5, 4, 7, 10, 9, 8, 6]
+        );
+    }
+
+    #[test]
+    fn can_remove() {
+        let values = vec![6, 4, 8, 2, 5, 1, 3, 7, 9, 10];
+        let mut tree = BackPointingBinaryTree::from_iter(values.into_iter());
+
+        // Leaf, node with one child, node with two children and finally the root
+        assert_eq!(tree.remove(&1), Some(1));
+        assert_eq!(tree.remove(&9), Some(9));
+        assert_eq!(tree.remove(&4), Some(4));
+        assert_eq!(tree.remove(&6), Some(6));
+        assert_eq!(tree.remove(&6), None);
+        assert_eq!(tree.size(), 6);
+
+        assert_parents_match(&tree);
+        assert_eq!(tree.to_vec_pre_order(), vec![5, 3, 2, 8, 7, 10]);
+
+        let mut tree = BackPointingBinaryTree::from_iter(vec![5, 5, 3, 5].into_iter());
+        assert_eq!(tree.remove(&5), Some(5));
+        assert_eq!(tree.remove(&5), Some(5));
+        assert_parents_match(&tree);
+        assert_eq!(tree.to_vec_in_order(), vec![3, 5]);
+
+        let mut tree = BackPointingBinaryTree::from_iter(vec![1].into_iter());
+        assert_eq!(tree.remove(&1), Some(1));
+        assert_eq!(tree.size(), 0);
+        assert!(tree.cursor().is_none());
+    }
+
+    #[test]
+    fn can_clear() {
+        let mut tree = BackPointingBinaryTree::from_iter(vec![2, 1, 3].into_iter());
+        tree.clear();
+        assert_eq!(tree.size(), 0);
+        assert_eq!(tree.to_vec_in_order(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn can_find_successor_and_predecessor() {
+        let tree =
+            BackPointingBinaryTree::from_iter(vec![6, 4, 8, 2, 5, 1, 3, 7, 9, 10].into_iter());
+
+        // Both within a subtree and through the parents
+        assert_eq!(tree.successor(&4), Some(5));
+        assert_eq!(tree.successor(&5), Some(6));
+        assert_eq!(tree.successor(&7), Some(8));
+        assert_eq!(tree.successor(&10), None);
+        assert_eq!(tree.predecessor(&6), Some(5));
+        assert_eq!(tree.predecessor(&7), Some(6));
+        assert_eq!(tree.predecessor(&3), Some(2));
+        assert_eq!(tree.predecessor(&1), None);
+        assert_eq!(tree.successor(&11), None);
+
+        // Duplicates are skipped
+        let tree = BackPointingBinaryTree::from_iter(vec![5, 3, 5, 8, 5].into_iter());
+        assert_eq!(tree.successor(&5), Some(8));
+        assert_eq!(tree.predecessor(&5), Some(3));
+    }
+
+    #[test]
+    fn can_move_cursor() {
+        let tree =
+            BackPointingBinaryTree::from_iter(vec![6, 4, 8, 2, 5, 1, 3, 7, 9, 10].into_iter());
+
+        let mut cursor = tree.cursor().unwrap();
+        assert_eq!(*cursor.current(), 6);
+        assert!(cursor.is_root());
+        assert!(!cursor.move_up());
+
+        assert!(cursor.move_left());
+        assert!(cursor.move_left());
+        assert!(cursor.move_right());
+        assert_eq!(*cursor.current(), 3);
+        assert!(!cursor.move_right());
+
+        assert!(cursor.move_up());
+        assert!(cursor.move_up());
+        assert_eq!(*cursor.current(), 4);
+
+        let mut cursor = tree.cursor_at(&1).unwrap();
+        let mut values = vec![*cursor.current()];
+        while cursor.move_next() {
+            values.push(*cursor.current());
+        }
+        assert_eq!(values, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+        assert!(cursor.move_prev());
+        assert_eq!(*cursor.current(), 9);
+        assert!(tree.cursor_at(&11).is_none());
+    }
+
+    #[test]
+    fn can_fmt_pretty() {
+        let tree =
+            BackPointingBinaryTree::from_iter(vec![6, 4, 8, 2, 5, 1, 3, 7, 9, 10].into_iter());
+
+        let expected = "        __6__
+       /     \\
+    __4       8
+   /   \\     / \\
+  2     5   7   9
+ / \\             \\
+1   3             10";
+
+        assert_eq!(format!("{}", tree), expected);
+        assert_eq!(format!("{}", BackPointingBinaryTree::<i32>::new()), "");
+    }
+
+    #[test]
+    fn can_fmt_empty_labels() {
+        let tree = BackPointingBinaryTree::from_iter(vec![String::new()].into_iter());
+        assert_eq!(format!("{}", tree), "");
+
+        let values = vec!["b".to_string(), String::new(), "c".to_string()];
+        let tree = BackPointingBinaryTree::from_iter(values.into_iter());
+        assert_eq!(format!("{}", tree), "  b\n / \\\n    c");
+    }
+
+    #[test]
+    fn can_clone_independently() {
+        let tree = BackPointingBinaryTree::from_iter(vec![6, 4, 8, 2, 5, 7].into_iter());
+
+        let mut clone = tree.clone();
+        assert_parents_match(&clone);
+        assert_eq!(clone.remove(&4), Some(4));
+        assert_eq!(clone.remove(&6), Some(6));
+        assert_eq!(clone.size(), 4);
+        assert_eq!(clone.to_vec_in_order(), vec![2, 5, 7, 8]);
+
+        // Taking the values out of the clone left the original untouched
+        assert_eq!(tree.size(), 6);
+        assert_eq!(tree.successor(&4), Some(5));
+        assert_eq!(format!("{}", tree), format!("{}", tree.clone()));
+        assert_eq!(tree.to_vec_in_order(), vec![2, 4, 5, 6, 7, 8]);
+    }
+
+    /// Checks that every child points back to its parent
+    fn assert_parents_match<T>(tree: &BackPointingBinaryTree<T>)
+    where
+        T: Debug + PartialEq + PartialOrd + Clone,
+    {
+        fn assert_parents_recur<T>(node: &Rc<RefCell<Node<T>>>)
+        where
+            T: Debug + PartialEq + PartialOrd + Clone,
+        {
+            let node_ref = node.as_ref().borrow();
+            for child in [&node_ref.left, &node_ref.right].into_iter().flatten() {
+                let parent = BackPointingBinaryTree::parent_of(child).unwrap();
+                assert!(Rc::ptr_eq(&parent, node));
+                assert_parents_recur(child);
+            }
+        }
+
+        if let Some(root) = &tree.root {
+            assert!(BackPointingBinaryTree::parent_of(root).is_none());
+            assert_parents_recur(root);
+        }
+    }
 }
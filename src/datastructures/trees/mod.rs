@@ -1,2 +1,5 @@
+pub mod avl_map;
 pub mod avl_tree;
 pub mod back_pointing_binary_tree;
+pub mod ordered_set;
+pub mod red_black_tree;
@@ -0,0 +1,201 @@
+/// A set that keeps its values in ascending order. Implemented by the balanced search trees,
+/// so the balancing strategy can be picked per workload.
+pub trait OrderedSet<T> {
+    type Iter<'a>: Iterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// Returns `false` and leaves the set unchanged if the value is already in it
+    fn insert(&mut self, value: T) -> bool;
+
+    fn remove(&mut self, value: &T) -> Option<T>;
+
+    fn contains(&self, value: &T) -> bool;
+
+    /// Iterates over the values in ascending order
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn min(&self) -> Option<&T>;
+
+    fn max(&self) -> Option<&T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+}
+
+/// Conformance tests every implementation has to pass
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::datastructures::trees::avl_tree::AVLTree;
+    use crate::datastructures::trees::red_black_tree::RedBlackTree;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn can_conform_as_avl_tree() {
+        check_conformance::<AVLTree<i32>>();
+        check_incomparable_values::<AVLTree<f64>>();
+    }
+
+    #[test]
+    fn can_conform_as_red_black_tree() {
+        check_conformance::<RedBlackTree<i32>>();
+        check_incomparable_values::<RedBlackTree<f64>>();
+    }
+
+    fn check_conformance<S>()
+    where
+        S: OrderedSet<i32> + Default,
+    {
+        check_empty::<S>();
+        check_insert_and_contains::<S>();
+        check_remove::<S>();
+        check_min_and_max::<S>();
+        check_iter::<S>();
+        check_random_operations::<S>();
+    }
+
+    fn check_empty<S>()
+    where
+        S: OrderedSet<i32> + Default,
+    {
+        let mut set = S::default();
+        assert!(set.is_empty());
+        assert_eq!(set.len(), 0);
+        assert!(!set.contains(&1));
+        assert_eq!(set.remove(&1), None);
+        assert_eq!(set.min(), None);
+        assert_eq!(set.max(), None);
+        assert_eq!(set.iter().next(), None);
+    }
+
+    fn check_insert_and_contains<S>()
+    where
+        S: OrderedSet<i32> + Default,
+    {
+        let mut set = S::default();
+        for value in [5, 3, 8, 1, 4] {
+            assert!(set.insert(value));
+        }
+
+        assert!(!set.insert(3));
+        assert!(!set.insert(8));
+        assert_eq!(set.len(), 5);
+        assert!(!set.is_empty());
+
+        for value in [1, 3, 4, 5, 8] {
+            assert!(set.contains(&value));
+        }
+        for value in [0, 2, 6, 7, 9] {
+            assert!(!set.contains(&value));
+        }
+    }
+
+    fn check_remove<S>()
+    where
+        S: OrderedSet<i32> + Default,
+    {
+        let mut set = S::default();
+        for value in 0..32 {
+            set.insert(value);
+        }
+
+        assert_eq!(set.remove(&16), Some(16));
+        assert_eq!(set.remove(&16), None);
+        assert_eq!(set.remove(&100), None);
+        assert_eq!(set.len(), 31);
+        assert!(!set.contains(&16));
+
+        for value in (0..32).filter(|value| *value != 16) {
+            assert_eq!(set.remove(&value), Some(value));
+        }
+        assert!(set.is_empty());
+
+        // The set is still usable after being emptied
+        assert!(set.insert(7));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![&7]);
+    }
+
+    fn check_min_and_max<S>()
+    where
+        S: OrderedSet<i32> + Default,
+    {
+        let mut set = S::default();
+        for value in [5, 3, 8, 1, 9, 7] {
+            set.insert(value);
+        }
+        assert_eq!(set.min(), Some(&1));
+        assert_eq!(set.max(), Some(&9));
+
+        set.remove(&1);
+        set.remove(&9);
+        assert_eq!(set.min(), Some(&3));
+        assert_eq!(set.max(), Some(&8));
+    }
+
+    fn check_iter<S>()
+    where
+        S: OrderedSet<i32> + Default,
+    {
+        let mut set = S::default();
+        for value in [6, 2, 9, 2, 4, 1, 8, 6] {
+            set.insert(value);
+        }
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 4, 6, 8, 9]
+        );
+
+        // Ascending inserts are the worst case for unbalanced trees
+        let mut set = S::default();
+        for value in 0..1000 {
+            set.insert(value);
+        }
+        assert!(set.iter().copied().eq(0..1000));
+    }
+
+    fn check_random_operations<S>()
+    where
+        S: OrderedSet<i32> + Default,
+    {
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut set = S::default();
+            let mut expected = BTreeSet::new();
+
+            for _ in 0..1000 {
+                let value = rng.gen_range(0..200);
+                if rng.gen_bool(0.6) {
+                    assert_eq!(set.insert(value), expected.insert(value));
+                } else {
+                    assert_eq!(set.remove(&value), expected.take(&value));
+                }
+                assert_eq!(set.len(), expected.len());
+            }
+
+            assert!(set.iter().eq(expected.iter()));
+            assert_eq!(set.min(), expected.first());
+            assert_eq!(set.max(), expected.last());
+        }
+    }
+
+    fn check_incomparable_values<S>()
+    where
+        S: OrderedSet<f64> + Default,
+    {
+        let mut set = S::default();
+        for value in [2.0, 1.0, 3.0] {
+            set.insert(value);
+        }
+
+        assert!(!set.contains(&f64::NAN));
+        assert_eq!(set.remove(&f64::NAN), None);
+        assert_eq!(set.len(), 3);
+    }
+}
@@ -0,0 +1,520 @@
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::mem;
+
+use super::ordered_set::OrderedSet;
+
+/// A left-leaning red-black tree, where red links only ever lean left. Compared to the
+/// `AVLTree` it is balanced less strictly, trading slightly deeper searches for fewer
+/// rotations on updates.
+pub struct RedBlackTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    root: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+#[derive(Debug)]
+struct Node<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    value: T,
+    /// Color of the link from the parent to this node
+    color: Color,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> Node<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            color: Color::Red,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+/// Public methods
+impl<T> RedBlackTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut tree = Self::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        return tree;
+    }
+
+    /// Returns `false` if the value is already in the tree, in which case it's dropped
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut inserted = false;
+        let mut root = Self::insert_node(self.root.take(), value, &mut inserted);
+        root.color = Color::Black;
+        self.root = Some(root);
+
+        if inserted {
+            self.len += 1;
+        }
+        return inserted;
+    }
+
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        if !self.contains(value) {
+            return None;
+        }
+
+        let mut root = self.root.take()?;
+        if !Self::is_red(&root.left) && !Self::is_red(&root.right) {
+            root.color = Color::Red;
+        }
+
+        let mut removed = None;
+        self.root = Self::remove_node(root, value, &mut removed);
+        if let Some(root) = &mut self.root {
+            root.color = Color::Black;
+        }
+
+        self.len -= 1;
+        return removed;
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut curr_node = &self.root;
+
+        while let Some(node) = curr_node {
+            if *value < node.value {
+                curr_node = &node.left;
+            } else if *value > node.value {
+                curr_node = &node.right;
+            } else {
+                return *value == node.value;
+            }
+        }
+
+        return false;
+    }
+
+    /// Returns the smallest value
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(left) = &node.left {
+            node = left;
+        }
+        return Some(&node.value);
+    }
+
+    /// Returns the largest value
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
+        }
+        return Some(&node.value);
+    }
+
+    pub fn height(&self) -> usize {
+        return Self::height_of(&self.root);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len == 0;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.len;
+    }
+
+    /// Iterates over the values in *inorder*
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            stack: Vec::new(),
+            len: self.len,
+        };
+        iter.push_left_path(&self.root);
+        return iter;
+    }
+}
+
+// Helper functions
+impl<T> RedBlackTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn is_red(node: &Link<T>) -> bool {
+        match node {
+            Some(node) => node.color == Color::Red,
+            None => false,
+        }
+    }
+
+    fn height_of(node: &Link<T>) -> usize {
+        match node {
+            Some(node) => 1 + Self::height_of(&node.left).max(Self::height_of(&node.right)),
+            None => 0,
+        }
+    }
+
+    fn insert_node(node: Link<T>, value: T, inserted: &mut bool) -> Box<Node<T>> {
+        let mut node = match node {
+            Some(node) => node,
+            None => {
+                *inserted = true;
+                return Box::new(Node::new(value));
+            }
+        };
+
+        if value < node.value {
+            node.left = Some(Self::insert_node(node.left.take(), value, inserted));
+        } else if value > node.value {
+            node.right = Some(Self::insert_node(node.right.take(), value, inserted));
+        }
+
+        return Self::balance(node);
+    }
+
+    /// Removes `value`, which has to be in the subtree, keeping a red link on the way down
+    /// so the removed node is never a black leaf
+    fn remove_node(mut node: Box<Node<T>>, value: &T, removed: &mut Option<T>) -> Link<T> {
+        if *value < node.value {
+            if !Self::is_red(&node.left) && !Self::left_is_red(&node.left) {
+                node = Self::move_red_left(node);
+            }
+            node.left = Self::remove_node(node.left.take().unwrap(), value, removed);
+        } else {
+            if Self::is_red(&node.left) {
+                node = Self::rotate_right(node);
+            }
+
+            if *value == node.value && node.right.is_none() {
+                *removed = Some(node.value);
+                return None;
+            }
+
+            if !Self::is_red(&node.right) && !Self::left_is_red(&node.right) {
+                node = Self::move_red_right(node);
+            }
+
+            if *value == node.value {
+                // Take over the value of the successor and remove its node instead
+                let mut min = None;
+                node.right = Self::remove_min_node(node.right.take().unwrap(), &mut min);
+                *removed = Some(mem::replace(&mut node.value, min.unwrap()));
+            } else {
+                node.right = Self::remove_node(node.right.take().unwrap(), value, removed);
+            }
+        }
+
+        return Some(Self::balance(node));
+    }
+
+    fn remove_min_node(mut node: Box<Node<T>>, removed: &mut Option<T>) -> Link<T> {
+        if node.left.is_none() {
+            *removed = Some(node.value);
+            return None;
+        }
+
+        if !Self::is_red(&node.left) && !Self::left_is_red(&node.left) {
+            node = Self::move_red_left(node);
+        }
+        node.left = Self::remove_min_node(node.left.take().unwrap(), removed);
+
+        return Some(Self::balance(node));
+    }
+
+    /// Whether the left child of `node` is red
+    fn left_is_red(node: &Link<T>) -> bool {
+        match node {
+            Some(node) => Self::is_red(&node.left),
+            None => false,
+        }
+    }
+
+    /// Restores the left-leaning invariants on the way back up
+    fn balance(mut node: Box<Node<T>>) -> Box<Node<T>> {
+        if Self::is_red(&node.right) && !Self::is_red(&node.left) {
+            node = Self::rotate_left(node);
+        }
+        if Self::is_red(&node.left) && Self::left_is_red(&node.left) {
+            node = Self::rotate_right(node);
+        }
+        if Self::is_red(&node.left) && Self::is_red(&node.right) {
+            Self::flip_colors(&mut node);
+        }
+
+        return node;
+    }
+
+    fn rotate_left(mut node: Box<Node<T>>) -> Box<Node<T>> {
+        let mut right = node.right.take().unwrap();
+        node.right = right.left.take();
+        right.color = node.color;
+        node.color = Color::Red;
+        right.left = Some(node);
+        return right;
+    }
+
+    fn rotate_right(mut node: Box<Node<T>>) -> Box<Node<T>> {
+        let mut left = node.left.take().unwrap();
+        node.left = left.right.take();
+        left.color = node.color;
+        node.color = Color::Red;
+        left.right = Some(node);
+        return left;
+    }
+
+    fn flip_colors(node: &mut Node<T>) {
+        node.color = Self::flipped(node.color);
+        for child in [&mut node.left, &mut node.right].into_iter().flatten() {
+            child.color = Self::flipped(child.color);
+        }
+    }
+
+    fn flipped(color: Color) -> Color {
+        match color {
+            Color::Red => Color::Black,
+            Color::Black => Color::Red,
+        }
+    }
+
+    /// Makes the left child or one of its children red, borrowing from the right sibling
+    /// if it has a red child to spare
+    fn move_red_left(mut node: Box<Node<T>>) -> Box<Node<T>> {
+        Self::flip_colors(&mut node);
+
+        if Self::left_is_red(&node.right) {
+            node.right = Some(Self::rotate_right(node.right.take().unwrap()));
+            node = Self::rotate_left(node);
+            Self::flip_colors(&mut node);
+        }
+
+        return node;
+    }
+
+    /// Makes the right child or one of its children red, borrowing from the left sibling
+    /// if it has a red child to spare
+    fn move_red_right(mut node: Box<Node<T>>) -> Box<Node<T>> {
+        Self::flip_colors(&mut node);
+
+        if Self::left_is_red(&node.left) {
+            node = Self::rotate_right(node);
+            Self::flip_colors(&mut node);
+        }
+
+        return node;
+    }
+}
+
+impl<T> OrderedSet<T> for RedBlackTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        return RedBlackTree::insert(self, value);
+    }
+
+    fn remove(&mut self, value: &T) -> Option<T> {
+        return RedBlackTree::remove(self, value);
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        return RedBlackTree::contains(self, value);
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        return RedBlackTree::iter(self);
+    }
+
+    fn min(&self) -> Option<&T> {
+        return RedBlackTree::min(self);
+    }
+
+    fn max(&self) -> Option<&T> {
+        return RedBlackTree::max(self);
+    }
+
+    fn len(&self) -> usize {
+        return RedBlackTree::len(self);
+    }
+}
+
+impl<T> Default for RedBlackTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for RedBlackTree<T>
+where
+    T: PartialEq + PartialOrd + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RedBlackTree<T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+pub struct Iter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    /// The nodes whose value and right subtree are still to be visited
+    stack: Vec<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    fn push_left_path(&mut self, mut curr_node: &'a Link<T>) {
+        while let Some(node) = curr_node {
+            self.stack.push(node);
+            curr_node = &node.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PartialEq + PartialOrd,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_path(&node.right);
+        self.len -= 1;
+        return Some(&node.value);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.len, Some(self.len));
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> where T: PartialEq + PartialOrd {}
+impl<T> FusedIterator for Iter<'_, T> where T: PartialEq + PartialOrd {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    #[test]
+    fn can_insert_and_remove() {
+        let mut tree = RedBlackTree::from_iter([5, 2, 8, 1, 3, 7, 9]);
+        assert_eq!(tree.len(), 7);
+        assert!(!tree.insert(3));
+        assert_invariants(&tree);
+
+        assert_eq!(tree.remove(&5), Some(5));
+        assert_eq!(tree.remove(&1), Some(1));
+        assert_eq!(tree.remove(&1), None);
+        assert_invariants(&tree);
+        assert_eq!(
+            tree.iter().copied().collect::<Vec<_>>(),
+            vec![2, 3, 7, 8, 9]
+        );
+        assert_eq!(format!("{:?}", tree), "{2, 3, 7, 8, 9}");
+    }
+
+    #[test]
+    fn can_stay_balanced() {
+        // A left-leaning red-black tree is at most 2 log2(n) high
+        let tree = RedBlackTree::from_iter(0..1023);
+        assert_invariants(&tree);
+        assert!(tree.height() <= 20);
+
+        let mut values: Vec<i32> = (0..1000).collect();
+        values.shuffle(&mut StdRng::seed_from_u64(0));
+
+        let mut tree = RedBlackTree::from_iter(values.iter().copied());
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(tree.remove(value), Some(*value));
+            if i % 50 == 0 {
+                assert_invariants(&tree);
+            }
+        }
+        assert!(tree.is_empty());
+    }
+
+    /// Checks that values are ordered, red links lean left and never follow each other, and
+    /// every path down from the root passes the same number of black links
+    fn assert_invariants<T>(tree: &RedBlackTree<T>)
+    where
+        T: PartialEq + PartialOrd + Debug,
+    {
+        fn black_height<T>(node: &Link<T>) -> usize
+        where
+            T: PartialEq + PartialOrd + Debug,
+        {
+            let node = match node {
+                Some(node) => node,
+                None => return 1,
+            };
+
+            assert!(!RedBlackTree::is_red(&node.right), "Red right link");
+            if node.color == Color::Red {
+                assert!(!RedBlackTree::is_red(&node.left), "Two red links in a row");
+            }
+
+            let left = black_height(&node.left);
+            let right = black_height(&node.right);
+            assert_eq!(left, right, "Unequal black heights below {:?}", node.value);
+
+            return left + (node.color == Color::Black) as usize;
+        }
+
+        assert!(!RedBlackTree::is_red(&tree.root), "Red root");
+        black_height(&tree.root);
+
+        let values: Vec<_> = tree.iter().collect();
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(values.len(), tree.len());
+    }
+}
@@ -1,2 +1,6 @@
 pub mod algorithms;
+pub mod allocators;
 pub mod datastructures;
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,32 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Counts how many times values sharing the same `counter` have been dropped. The value
+/// owns heap memory, so Miri also catches leaks and double frees of the collections
+/// holding it.
+#[derive(Debug)]
+pub(crate) struct DropCounter {
+    pub(crate) value: Box<i32>,
+    counter: Rc<Cell<usize>>,
+}
+
+impl DropCounter {
+    pub(crate) fn new(value: i32, counter: &Rc<Cell<usize>>) -> Self {
+        Self {
+            value: Box::new(value),
+            counter: Rc::clone(counter),
+        }
+    }
+}
+
+impl PartialEq for DropCounter {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.counter.set(self.counter.get() + 1);
+    }
+}